@@ -0,0 +1,121 @@
+//! Integer arithmetic for solution hot paths. With the `checked-arith` feature an
+//! overflow panics naming the operation and operands, otherwise these compile down
+//! to the plain operators (which wrap silently in release builds).
+
+use std::{
+    fmt::Display,
+    ops::{Add, Mul, Sub},
+};
+
+pub trait Int:
+    Copy + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+    fn pow(self, exp: u32) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const ZERO: Self = 0;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_pow(self, exp: u32) -> Option<Self> {
+                    <$t>::checked_pow(self, exp)
+                }
+
+                fn pow(self, exp: u32) -> Self {
+                    <$t>::pow(self, exp)
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+#[cfg(feature = "checked-arith")]
+#[cold]
+#[track_caller]
+fn overflow<T: Int>(op: &str, a: T, b: impl Display) -> ! {
+    panic!(
+        "arithmetic overflow: {} {} {} ({})",
+        a,
+        op,
+        b,
+        std::any::type_name::<T>()
+    );
+}
+
+#[inline]
+#[track_caller]
+pub fn add<T: Int>(a: T, b: T) -> T {
+    #[cfg(feature = "checked-arith")]
+    return match a.checked_add(b) {
+        Some(v) => v,
+        None => overflow("+", a, b),
+    };
+    #[cfg(not(feature = "checked-arith"))]
+    return a + b;
+}
+
+#[inline]
+#[track_caller]
+pub fn sub<T: Int>(a: T, b: T) -> T {
+    #[cfg(feature = "checked-arith")]
+    return match a.checked_sub(b) {
+        Some(v) => v,
+        None => overflow("-", a, b),
+    };
+    #[cfg(not(feature = "checked-arith"))]
+    return a - b;
+}
+
+#[inline]
+#[track_caller]
+pub fn mul<T: Int>(a: T, b: T) -> T {
+    #[cfg(feature = "checked-arith")]
+    return match a.checked_mul(b) {
+        Some(v) => v,
+        None => overflow("*", a, b),
+    };
+    #[cfg(not(feature = "checked-arith"))]
+    return a * b;
+}
+
+#[inline]
+#[track_caller]
+pub fn pow<T: Int>(base: T, exp: u32) -> T {
+    #[cfg(feature = "checked-arith")]
+    return match base.checked_pow(exp) {
+        Some(v) => v,
+        None => overflow("^", base, exp),
+    };
+    #[cfg(not(feature = "checked-arith"))]
+    return base.pow(exp);
+}
+
+/// Sum of `values` using [`add`]
+#[track_caller]
+pub fn sum<T: Int>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, add)
+}
@@ -0,0 +1,199 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Only look at the clock every this many checks
+const CLOCK_INTERVAL: u64 = 64;
+
+/// Cooperative cancellation for long-running loops. Loops call [`CancelToken::check`]
+/// once per unit of work and bail out with the error once the deadline has passed
+/// or [`CancelToken::cancel`] was called.
+#[derive(Debug, Default)]
+pub struct CancelToken {
+    deadline: Option<Instant>,
+    steps: AtomicU64,
+    cancelled: AtomicBool,
+    timed_out: AtomicBool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cancelled {
+    /// Checks made before giving up
+    pub steps: u64,
+    pub timed_out: bool,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.timed_out {
+            write!(f, "timed out after {} steps", self.steps)
+        } else {
+            write!(f, "cancelled after {} steps", self.steps)
+        }
+    }
+}
+
+impl Error for Cancelled {}
+
+impl CancelToken {
+    /// A token that is only cancelled explicitly
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deadline(deadline: Instant) -> Self {
+        CancelToken {
+            deadline: Some(deadline),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Token with the `--timeout <seconds>` from the command line, if any.
+    /// Exits with the error if the timeout is missing or not a valid duration.
+    pub fn from_args() -> Self {
+        match timeout_from_args(std::env::args()) {
+            Ok(Some(timeout)) => Self::with_timeout(timeout),
+            Ok(None) => Self::new(),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    /// Count one step of work, failing if the token has been cancelled
+    pub fn check(&self) -> Result<(), Cancelled> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if let Some(deadline) = self.deadline
+            && steps.is_multiple_of(CLOCK_INTERVAL)
+            && Instant::now() >= deadline
+        {
+            // Make later checks fail without looking at the clock
+            self.timed_out.store(true, Ordering::Relaxed);
+            self.cancel();
+        }
+
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(Cancelled {
+                steps,
+                timed_out: self.timed_out.load(Ordering::Relaxed),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// A `--timeout` without a value, or with one that isn't a valid number of seconds
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidTimeout {
+    /// The value given, `None` if it was missing
+    pub value: Option<String>,
+}
+
+impl fmt::Display for InvalidTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "invalid --timeout {:?}", value)?,
+            None => write!(f, "missing --timeout value")?,
+        }
+        write!(f, ", expected a non-negative number of seconds")
+    }
+}
+
+impl Error for InvalidTimeout {}
+
+/// Parse `--timeout <seconds>` or `--timeout=<seconds>`, seconds may be fractional
+pub fn timeout_from_args(
+    args: impl IntoIterator<Item = String>,
+) -> Result<Option<Duration>, InvalidTimeout> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let secs = if arg == "--timeout" {
+            args.next()
+        } else if let Some(secs) = arg.strip_prefix("--timeout=") {
+            Some(secs.to_string())
+        } else {
+            continue;
+        };
+
+        let Some(secs) = secs else {
+            return Err(InvalidTimeout { value: None });
+        };
+        return secs
+            .parse()
+            .ok()
+            .and_then(|s| Duration::try_from_secs_f64(s).ok())
+            .map(Some)
+            .ok_or(InvalidTimeout { value: Some(secs) });
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Duration>, InvalidTimeout> {
+        timeout_from_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_both_forms() {
+        assert_eq!(parse(&["day6"]), Ok(None));
+        assert_eq!(
+            parse(&["day6", "--timeout", "1.5"]),
+            Ok(Some(Duration::from_millis(1500)))
+        );
+        assert_eq!(parse(&["day6", "--timeout=0"]), Ok(Some(Duration::ZERO)));
+    }
+
+    #[test]
+    fn rejects_missing_and_invalid_values() {
+        let invalid = |value: &str| {
+            Err(InvalidTimeout {
+                value: Some(value.to_string()),
+            })
+        };
+        assert_eq!(
+            parse(&["day6", "--timeout"]),
+            Err(InvalidTimeout { value: None })
+        );
+        assert_eq!(parse(&["day6", "--timeout=abc"]), invalid("abc"));
+        assert_eq!(parse(&["day6", "--timeout", "-1"]), invalid("-1"));
+        assert_eq!(parse(&["day6", "--timeout=NaN"]), invalid("NaN"));
+        assert_eq!(parse(&["day6", "--timeout=1e30"]), invalid("1e30"));
+    }
+
+    #[test]
+    fn error_message() {
+        assert_eq!(
+            InvalidTimeout { value: None }.to_string(),
+            "missing --timeout value, expected a non-negative number of seconds"
+        );
+        assert_eq!(
+            InvalidTimeout {
+                value: Some("-1".to_string())
+            }
+            .to_string(),
+            "invalid --timeout \"-1\", expected a non-negative number of seconds"
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Borrowing iterators over slices. Items are references (or fixed size arrays of
+//! references), so nothing is allocated per item.
+
+/// Unordered pairs `(items[i], items[j])` with `i < j`
+pub fn pairs<T>(items: &[T]) -> Pairs<'_, T> {
+    Pairs { items, i: 0, j: 1 }
+}
+
+pub struct Pairs<'a, T> {
+    items: &'a [T],
+    i: usize,
+    j: usize,
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.j >= self.items.len() {
+            self.i += 1;
+            self.j = self.i + 1;
+            if self.j >= self.items.len() {
+                return None;
+            }
+        }
+
+        let item = (&self.items[self.i], &self.items[self.j]);
+        self.j += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.items.len();
+        if self.j >= n && self.i + 2 >= n {
+            return (0, Some(0));
+        }
+        // Remaining in the current row, plus all pairs starting after row i
+        let rest = n - self.i - 1;
+        let remaining = (n - self.j) + (rest * (rest - 1)) / 2;
+        (remaining, Some(remaining))
+    }
+}
+
+/// `K`-combinations of `items` in lexicographic index order
+pub fn combinations<T, const K: usize>(items: &[T]) -> Combinations<'_, T, K> {
+    Combinations {
+        items,
+        idxs: std::array::from_fn(|i| i),
+        done: K > items.len(),
+    }
+}
+
+pub struct Combinations<'a, T, const K: usize> {
+    items: &'a [T],
+    idxs: [usize; K],
+    done: bool,
+}
+
+impl<'a, T, const K: usize> Iterator for Combinations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.idxs.map(|i| &self.items[i]);
+
+        // Advance the rightmost index that still has room, then reset everything after it
+        let n = self.items.len();
+        match (0..K).rev().find(|&i| self.idxs[i] < n - K + i) {
+            Some(i) => {
+                self.idxs[i] += 1;
+                for j in i + 1..K {
+                    self.idxs[j] = self.idxs[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(item)
+    }
+}
+
+/// `K`-permutations (ordered selections without repetition) of `items`
+/// in lexicographic index order
+pub fn permutations<T, const K: usize>(items: &[T]) -> Permutations<'_, T, K> {
+    Permutations {
+        items,
+        idxs: std::array::from_fn(|i| i),
+        done: K > items.len(),
+    }
+}
+
+pub struct Permutations<'a, T, const K: usize> {
+    items: &'a [T],
+    idxs: [usize; K],
+    done: bool,
+}
+
+impl<T, const K: usize> Permutations<'_, T, K> {
+    fn next_unused(&self, pos: usize, after: Option<usize>) -> Option<usize> {
+        let start = after.map_or(0, |a| a + 1);
+        (start..self.items.len()).find(|v| !self.idxs[..pos].contains(v))
+    }
+
+    fn advance(&mut self) -> bool {
+        for i in (0..K).rev() {
+            if let Some(v) = self.next_unused(i, Some(self.idxs[i])) {
+                self.idxs[i] = v;
+                for j in i + 1..K {
+                    // There are always enough unused values since K <= n
+                    self.idxs[j] = self.next_unused(j, None).unwrap();
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<'a, T, const K: usize> Iterator for Permutations<'a, T, K> {
+    type Item = [&'a T; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.idxs.map(|i| &self.items[i]);
+        self.done = !self.advance();
+
+        Some(item)
+    }
+}
+
+/// Cartesian product `(a[i], b[j])`, with `b` varying fastest
+pub fn product<'a, A, B>(a: &'a [A], b: &'a [B]) -> Product<'a, A, B> {
+    Product { a, b, i: 0, j: 0 }
+}
+
+pub struct Product<'a, A, B> {
+    a: &'a [A],
+    b: &'a [B],
+    i: usize,
+    j: usize,
+}
+
+impl<'a, A, B> Iterator for Product<'a, A, B> {
+    type Item = (&'a A, &'a B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.j >= self.b.len() {
+            self.i += 1;
+            self.j = 0;
+        }
+        if self.i >= self.a.len() || self.b.is_empty() {
+            return None;
+        }
+
+        let item = (&self.a[self.i], &self.b[self.j]);
+        self.j += 1;
+        Some(item)
+    }
+}
+
+/// Adjacent pairs `(items[i], items[i + 1])`
+pub fn pairwise<T>(items: &[T]) -> impl ExactSizeIterator<Item = (&T, &T)> {
+    items.windows(2).map(|w| (&w[0], &w[1]))
+}
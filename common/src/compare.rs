@@ -0,0 +1,136 @@
+use std::fmt::{self, Debug};
+
+use crate::rng::Rng;
+
+type Implementation<I, O> = Box<dyn Fn(&I) -> O>;
+
+/// Runs several implementations of the same solution over a set of inputs and
+/// reports any input on which they disagree
+pub struct Comparison<I, O> {
+    implementations: Vec<(&'static str, Implementation<I, O>)>,
+    inputs: Vec<(String, I)>,
+}
+
+/// Inputs on which the implementations disagreed. Inputs and outputs are kept in
+/// their `Debug` form.
+pub struct Mismatch {
+    pub label: String,
+    pub input: String,
+    pub outputs: Vec<(&'static str, String)>,
+}
+
+pub struct CompareReport {
+    pub implementations: Vec<&'static str>,
+    pub input_count: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl<I, O> Comparison<I, O>
+where
+    I: Debug,
+    O: PartialEq + Debug,
+{
+    pub fn new() -> Self {
+        Comparison {
+            implementations: Vec::new(),
+            inputs: Vec::new(),
+        }
+    }
+
+    pub fn implementation(mut self, name: &'static str, f: impl Fn(&I) -> O + 'static) -> Self {
+        self.implementations.push((name, Box::new(f)));
+        self
+    }
+
+    pub fn input(mut self, label: impl Into<String>, input: I) -> Self {
+        self.inputs.push((label.into(), input));
+        self
+    }
+
+    /// Add `count` inputs from `generate`, seeded so a failing input can be reproduced
+    pub fn generated(
+        mut self,
+        label: &str,
+        count: usize,
+        seed: u64,
+        mut generate: impl FnMut(&mut Rng) -> I,
+    ) -> Self {
+        let mut rng = Rng::new(seed);
+        for i in 0..count {
+            self.inputs.push((
+                format!("{} #{} (seed {})", label, i, seed),
+                generate(&mut rng),
+            ));
+        }
+        self
+    }
+
+    pub fn run(&self) -> CompareReport {
+        let mismatches = self
+            .inputs
+            .iter()
+            .filter_map(|(label, input)| {
+                let outputs = self
+                    .implementations
+                    .iter()
+                    .map(|(name, f)| (*name, f(input)))
+                    .collect::<Vec<_>>();
+                if outputs.windows(2).all(|w| w[0].1 == w[1].1) {
+                    return None;
+                }
+
+                Some(Mismatch {
+                    label: label.clone(),
+                    input: format!("{:?}", input),
+                    outputs: outputs
+                        .into_iter()
+                        .map(|(name, o)| (name, format!("{:?}", o)))
+                        .collect(),
+                })
+            })
+            .collect();
+
+        CompareReport {
+            implementations: self.implementations.iter().map(|(name, _)| *name).collect(),
+            input_count: self.inputs.len(),
+            mismatches,
+        }
+    }
+}
+
+impl<I, O> Default for Comparison<I, O>
+where
+    I: Debug,
+    O: PartialEq + Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompareReport {
+    pub fn ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for CompareReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "compared {} on {} inputs: {} mismatches",
+            self.implementations.join(", "),
+            self.input_count,
+            self.mismatches.len()
+        )?;
+        for m in &self.mismatches {
+            writeln!(f, "mismatch on {}", m.label)?;
+            writeln!(f, "  input: {}", m.input)?;
+            for (name, output) in &m.outputs {
+                writeln!(f, "  {}: {}", name, output)?;
+            }
+        }
+
+        Ok(())
+    }
+}
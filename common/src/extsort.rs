@@ -0,0 +1,314 @@
+//! External-memory sorting and counting for inputs that do not fit in RAM. Values
+//! are buffered in fixed-size chunks, each chunk is sorted and written to a run
+//! file in a temporary directory, and the runs are k-way merged when read back.
+//! Only one buffered chunk and one value per run are held in memory.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Most runs merged at once, so the open files stay well under the usual limits
+const MAX_FAN_IN: usize = 128;
+
+/// Fixed-size records that can be written to run files
+pub trait Record: Ord + Copy {
+    fn write_to(&self, w: &mut impl Write) -> io::Result<()>;
+    /// `None` at a clean end of file
+    fn read_from(r: &mut impl Read) -> io::Result<Option<Self>>;
+}
+
+impl Record for u64 {
+    fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.to_le_bytes())
+    }
+
+    fn read_from(r: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut buf = [0; 8];
+        match r.read_exact(&mut buf) {
+            Ok(()) => Ok(Some(u64::from_le_bytes(buf))),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Record for (u64, u64) {
+    fn write_to(&self, w: &mut impl Write) -> io::Result<()> {
+        self.0.write_to(w)?;
+        self.1.write_to(w)
+    }
+
+    fn read_from(r: &mut impl Read) -> io::Result<Option<Self>> {
+        let Some(a) = u64::read_from(r)? else {
+            return Ok(None);
+        };
+        let b = u64::read_from(r)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated record"))?;
+        Ok(Some((a, b)))
+    }
+}
+
+/// Temporary directory for run files, removed with everything in it on drop
+pub struct SpillDir {
+    path: PathBuf,
+    next_run: usize,
+}
+
+impl SpillDir {
+    /// A fresh directory under `parent`
+    pub fn new_in(parent: impl AsRef<Path>) -> io::Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = parent.as_ref().join(format!(
+            "spill-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&path)?;
+        Ok(SpillDir { path, next_run: 0 })
+    }
+
+    /// A fresh directory under the system temporary directory
+    pub fn new() -> io::Result<Self> {
+        Self::new_in(std::env::temp_dir())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write `records` to a new run file
+    fn write_run<T: Record>(&mut self, records: &[T]) -> io::Result<PathBuf> {
+        self.write_run_from(records.iter().map(|r| Ok(*r)))
+    }
+
+    fn write_run_from<T: Record>(
+        &mut self,
+        records: impl Iterator<Item = io::Result<T>>,
+    ) -> io::Result<PathBuf> {
+        let path = self.path.join(format!("run-{}", self.next_run));
+        self.next_run += 1;
+
+        let mut w = BufWriter::new(File::create(&path)?);
+        for r in records {
+            r?.write_to(&mut w)?;
+        }
+        w.flush()?;
+        Ok(path)
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Sorts any number of records holding at most `chunk_len` of them in memory
+pub struct ExternalSorter<T> {
+    dir: SpillDir,
+    chunk_len: usize,
+    chunk: Vec<T>,
+    runs: Vec<PathBuf>,
+}
+
+impl<T: Record> ExternalSorter<T> {
+    pub fn new(dir: SpillDir, chunk_len: usize) -> Self {
+        assert!(chunk_len > 0, "chunk length must be positive");
+        ExternalSorter {
+            dir,
+            chunk_len,
+            chunk: Vec::with_capacity(chunk_len),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, record: T) -> io::Result<()> {
+        self.chunk.push(record);
+        if self.chunk.len() == self.chunk_len {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        self.chunk.sort_unstable();
+        let run = self.dir.write_run(&self.chunk)?;
+        self.runs.push(run);
+        self.chunk.clear();
+        Ok(())
+    }
+
+    /// Write out the last chunk, the runs can then be merged any number of times.
+    /// Runs are merged into longer ones until at most [`MAX_FAN_IN`] are left.
+    pub fn finish(mut self) -> io::Result<SortedRuns<T>> {
+        if !self.chunk.is_empty() {
+            self.spill()?;
+        }
+
+        while self.runs.len() > MAX_FAN_IN {
+            let mut merged = Vec::new();
+            for group in self.runs.chunks(MAX_FAN_IN) {
+                let path = self.dir.write_run_from(merge_runs::<T>(group)?)?;
+                merged.push(path);
+            }
+            for path in self.runs.drain(..) {
+                fs::remove_file(path)?;
+            }
+            self.runs = merged;
+        }
+
+        Ok(SortedRuns {
+            dir: self.dir,
+            runs: self.runs,
+            _record: PhantomData,
+        })
+    }
+}
+
+/// Sorted run files, removed when this is dropped
+pub struct SortedRuns<T> {
+    dir: SpillDir,
+    runs: Vec<PathBuf>,
+    _record: PhantomData<T>,
+}
+
+impl<T: Record> SortedRuns<T> {
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// K-way merge of the runs into one sorted stream
+    pub fn merge(&self) -> io::Result<Merge<T>> {
+        merge_runs(&self.runs)
+    }
+}
+
+fn merge_runs<T: Record>(runs: &[PathBuf]) -> io::Result<Merge<T>> {
+    let mut merge = Merge {
+        readers: Vec::new(),
+        heap: BinaryHeap::new(),
+    };
+    for path in runs {
+        let mut reader = BufReader::new(File::open(path)?);
+        if let Some(first) = T::read_from(&mut reader)? {
+            merge.heap.push(Reverse((first, merge.readers.len())));
+        }
+        merge.readers.push(reader);
+    }
+
+    Ok(merge)
+}
+
+pub struct Merge<T> {
+    readers: Vec<BufReader<File>>,
+    /// Smallest unread record of each run that has one left, with its run index
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+}
+
+impl<T: Record> Iterator for Merge<T> {
+    type Item = io::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((record, run)) = self.heap.pop()?;
+        match T::read_from(&mut self.readers[run]) {
+            Ok(Some(next)) => self.heap.push(Reverse((next, run))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+
+        Some(Ok(record))
+    }
+}
+
+/// Counts occurrences of values holding at most `max_keys` distinct values in
+/// memory. When the map is full it is spilled as a sorted run of (value, count).
+pub struct SpillingCounter {
+    sorter: ExternalSorter<(u64, u64)>,
+    max_keys: usize,
+    counts: HashMap<u64, u64>,
+}
+
+impl SpillingCounter {
+    pub fn new(dir: SpillDir, max_keys: usize) -> Self {
+        SpillingCounter {
+            sorter: ExternalSorter::new(dir, max_keys),
+            max_keys,
+            counts: HashMap::new(),
+        }
+    }
+
+    pub fn add(&mut self, value: u64) -> io::Result<()> {
+        *self.counts.entry(value).or_default() += 1;
+        if self.counts.len() == self.max_keys {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        for entry in self.counts.drain() {
+            self.sorter.push(entry)?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> io::Result<Counts> {
+        self.spill()?;
+        Ok(Counts {
+            runs: self.sorter.finish()?,
+        })
+    }
+}
+
+/// Spilled counts, read back as (value, count) in value order
+pub struct Counts {
+    runs: SortedRuns<(u64, u64)>,
+}
+
+impl Counts {
+    pub fn iter(&self) -> io::Result<CountIter> {
+        Ok(CountIter {
+            merge: self.runs.merge()?,
+            pending: None,
+        })
+    }
+}
+
+/// Merged counts with the entries for the same value from different runs summed
+pub struct CountIter {
+    merge: Merge<(u64, u64)>,
+    pending: Option<(u64, u64)>,
+}
+
+impl Iterator for CountIter {
+    type Item = io::Result<(u64, u64)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match (self.merge.next(), self.pending) {
+                (Some(Err(e)), _) => return Some(Err(e)),
+                (Some(Ok((value, count))), Some((pending, total))) if value == pending => {
+                    self.pending = Some((pending, total + count));
+                }
+                (Some(Ok(entry)), pending) => {
+                    self.pending = Some(entry);
+                    if let Some(done) = pending {
+                        return Some(Ok(done));
+                    }
+                }
+                (None, _) => return self.pending.take().map(Ok),
+            }
+        }
+    }
+}
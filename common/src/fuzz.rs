@@ -0,0 +1,308 @@
+//! Mutation fuzzing for input parsers. Starting from real and example inputs, each
+//! run mutates a seed and checks the parser returns (`Ok` or `Err`) rather than
+//! panicking. Panicking inputs can be saved to a directory and replayed later as
+//! regression fixtures.
+//!
+//! Each day's `tests/fuzz.rs` is one [`parser_fuzz_test!`](crate::parser_fuzz_test)
+//! call, which saves new crashes under the build's `target` directory.
+
+use std::{
+    fmt, fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use crate::rng::Rng;
+
+/// Bytes that tend to matter to the parsers
+const INTERESTING: &[u8] = b"0123456789-+ ,:=\n\r\t.#@^<>[]()SEOXpv";
+
+pub struct Fuzzer {
+    rng: Rng,
+    seeds: Vec<Vec<u8>>,
+    crash_dir: Option<PathBuf>,
+}
+
+pub struct Crash {
+    pub input: Vec<u8>,
+    pub message: String,
+    /// Where the input was saved, if it was
+    pub saved: Option<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct FuzzReport {
+    pub runs: usize,
+    pub ok: usize,
+    pub errors: usize,
+    pub crashes: Vec<Crash>,
+}
+
+impl Fuzzer {
+    pub fn new(seed: u64) -> Self {
+        Fuzzer {
+            rng: Rng::new(seed),
+            seeds: Vec::new(),
+            crash_dir: None,
+        }
+    }
+
+    /// Add an input to start mutating from
+    pub fn seed_input(mut self, input: impl AsRef<[u8]>) -> Self {
+        self.seeds.push(input.as_ref().to_vec());
+        self
+    }
+
+    /// Save each crashing input to `dir`, named after a hash of its contents
+    pub fn crash_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.crash_dir = Some(dir.into());
+        self
+    }
+
+    /// Run `target` on `iterations` mutated inputs. Bytes that are not UTF-8 are
+    /// replaced before the input is handed to `target`.
+    pub fn run<T, E>(
+        &mut self,
+        iterations: usize,
+        target: impl Fn(&str) -> Result<T, E>,
+    ) -> FuzzReport {
+        assert!(!self.seeds.is_empty(), "no seed inputs");
+
+        let mut report = FuzzReport::default();
+        let hook = silence_panics();
+        for _ in 0..iterations {
+            let seed = &self.seeds[self.rng.below(self.seeds.len() as u64) as usize];
+            let mut input = seed.clone();
+            // Stack a few mutations so they can interact
+            for _ in 0..=self.rng.below(4) {
+                mutate(&mut self.rng, &mut input);
+            }
+
+            report.runs += 1;
+            match check(&target, &input) {
+                Ok(true) => report.ok += 1,
+                Ok(false) => report.errors += 1,
+                Err(message) => {
+                    let saved = self.crash_dir.as_ref().map(|dir| save(dir, &input));
+                    report.crashes.push(Crash {
+                        input,
+                        message,
+                        saved,
+                    });
+                }
+            }
+        }
+        panic::set_hook(hook);
+
+        report
+    }
+}
+
+/// A `parser_never_panics` test for a day's parser. It replays the
+/// `regressions` inputs (byte strings of crashes worth keeping), then fuzzes
+/// from the named input files in the crate directory with `seed`. New crashes
+/// are saved under the cargo target directory, not the source tree.
+#[macro_export]
+macro_rules! parser_fuzz_test {
+    (
+        $parse:path,
+        seed = $seed:expr,
+        inputs = [$($file:literal),+ $(,)?]
+        $(, regressions = [$($regression:expr),* $(,)?])?
+        $(,)?
+    ) => {
+        #[test]
+        fn parser_never_panics() {
+            $crate::fuzz::check_parser(
+                $parse,
+                $seed,
+                &[$(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $file))),+],
+                &[$($(&$regression[..]),*)?],
+                concat!(env!("CARGO_TARGET_TMPDIR"), "/fuzz-crashes"),
+            );
+        }
+    };
+}
+
+/// Panic with a report if `parse` panics on any of `regressions`, or on any of
+/// 1000 inputs mutated from `seeds`. Crashing inputs are saved to `crash_dir`.
+pub fn check_parser<T, E>(
+    parse: impl Fn(&str) -> Result<T, E>,
+    seed: u64,
+    seeds: &[&str],
+    regressions: &[&[u8]],
+    crash_dir: impl Into<PathBuf>,
+) {
+    let hook = silence_panics();
+    let still_crashing = regressions
+        .iter()
+        .filter_map(|input| check(&parse, input).err())
+        .collect::<Vec<_>>();
+    panic::set_hook(hook);
+    assert!(
+        still_crashing.is_empty(),
+        "{} regression inputs still panic: {:?}",
+        still_crashing.len(),
+        still_crashing
+    );
+
+    let mut fuzzer = Fuzzer::new(seed).crash_dir(crash_dir);
+    for input in seeds {
+        fuzzer = fuzzer.seed_input(input);
+    }
+    let report = fuzzer.run(1000, parse);
+    assert!(report.crashes.is_empty(), "{}", report);
+}
+
+/// Run `target` on every file in `dir`, returning the ones that still panic.
+/// A missing directory has no fixtures.
+pub fn replay<T, E>(dir: impl AsRef<Path>, target: impl Fn(&str) -> Result<T, E>) -> Vec<Crash> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths = entries
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let hook = silence_panics();
+    let crashes = paths
+        .into_iter()
+        .filter_map(|path| {
+            let input = fs::read(&path).unwrap();
+            check(&target, &input).err().map(|message| Crash {
+                input,
+                message,
+                saved: Some(path),
+            })
+        })
+        .collect();
+    panic::set_hook(hook);
+
+    crashes
+}
+
+/// Apply one random mutation to `input`
+pub fn mutate(rng: &mut Rng, input: &mut Vec<u8>) {
+    let len = input.len() as u64;
+    let pos = |rng: &mut Rng| rng.below(len.max(1)) as usize;
+    let byte = |rng: &mut Rng| {
+        if rng.chance(0.8) {
+            INTERESTING[rng.below(INTERESTING.len() as u64) as usize]
+        } else {
+            rng.below(256) as u8
+        }
+    };
+
+    match rng.below(7) {
+        // Replace a byte
+        0 if len > 0 => {
+            let i = pos(rng);
+            input[i] = byte(rng);
+        }
+        // Insert a byte
+        1 => {
+            let i = rng.below(len + 1) as usize;
+            let b = byte(rng);
+            input.insert(i, b);
+        }
+        // Delete a short range
+        2 if len > 0 => {
+            let i = pos(rng);
+            let n = (rng.range(1..9) as usize).min(input.len() - i);
+            input.drain(i..i + n);
+        }
+        // Duplicate a short range
+        3 if len > 0 => {
+            let i = pos(rng);
+            let n = (rng.range(1..17) as usize).min(input.len() - i);
+            let chunk = input[i..i + n].to_vec();
+            let at = rng.below(input.len() as u64 + 1) as usize;
+            input.splice(at..at, chunk);
+        }
+        // Truncate
+        4 => {
+            let i = rng.below(len + 1) as usize;
+            input.truncate(i);
+        }
+        // Swap two bytes
+        5 if len > 1 => {
+            let (i, j) = (pos(rng), pos(rng));
+            input.swap(i, j);
+        }
+        // Replace a run of digits with a large or odd number
+        _ => {
+            let i = pos(rng);
+            let number: &[u8] = match rng.below(4) {
+                0 => b"0",
+                1 => b"99999999999999999999999",
+                2 => b"-1",
+                _ => b"18446744073709551615",
+            };
+            let end = input
+                .iter()
+                .skip(i)
+                .position(|b| !b.is_ascii_digit())
+                .map_or(input.len(), |n| i + n);
+            input.splice(i.min(end)..end, number.iter().copied());
+        }
+    }
+}
+
+/// Ok(true) for `Ok`, Ok(false) for `Err` and the panic message if `target` panicked
+fn check<T, E>(target: &impl Fn(&str) -> Result<T, E>, input: &[u8]) -> Result<bool, String> {
+    let text = String::from_utf8_lossy(input);
+
+    panic::catch_unwind(AssertUnwindSafe(|| target(&text).is_ok())).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic".to_string())
+    })
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Keep the panic hook from printing every expected panic, returning the old hook
+fn silence_panics() -> PanicHook {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    hook
+}
+
+fn save(dir: &Path, input: &[u8]) -> PathBuf {
+    // FNV-1a, stable across runs so the same crash is only saved once
+    let hash = input.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, &b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    let path = dir.join(format!("crash-{:016x}", hash));
+
+    fs::create_dir_all(dir).unwrap();
+    fs::write(&path, input).unwrap();
+    path
+}
+
+impl fmt::Display for FuzzReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} runs: {} ok, {} errors, {} crashes",
+            self.runs,
+            self.ok,
+            self.errors,
+            self.crashes.len()
+        )?;
+        for crash in &self.crashes {
+            write!(f, "  panicked: {}", crash.message)?;
+            if let Some(path) = &crash.saved {
+                write!(f, " (saved to {})", path.display())?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
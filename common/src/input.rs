@@ -0,0 +1,156 @@
+//! Opening puzzle input files, optionally from copies baked into the binary or
+//! from a cache directory keyed by puzzle
+
+use std::{
+    fs::{self, File},
+    io::{self, BufRead},
+    path::{Path, PathBuf},
+};
+
+use crate::puzzle::PuzzleId;
+
+/// `(file name, contents)` of inputs compiled into a binary
+pub type Embedded = &'static [(&'static str, &'static str)];
+
+/// Define `EMBEDDED` in the calling crate with the named files from its crate
+/// directory when its `embed-inputs` feature is on, and empty otherwise
+#[macro_export]
+macro_rules! embed_inputs {
+    ($($name:literal),* $(,)?) => {
+        #[cfg(feature = "embed-inputs")]
+        const EMBEDDED: $crate::input::Embedded = &[$(
+            ($name, include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $name))),
+        )*];
+        #[cfg(not(feature = "embed-inputs"))]
+        const EMBEDDED: $crate::input::Embedded = &[];
+    };
+}
+
+/// Buffered reader over `filename` of `puzzle`. A name found in `embedded` is read
+/// from memory instead, so binaries with embedded inputs work from any directory.
+/// With an [`InputCache`] configured, a file read from disk is copied into it and
+/// a file missing from the working directory is read from it.
+pub fn open(puzzle: PuzzleId, filename: impl AsRef<Path>, embedded: Embedded) -> Box<dyn BufRead> {
+    open_with(
+        InputCache::from_env().as_ref(),
+        puzzle,
+        filename.as_ref(),
+        embedded,
+    )
+}
+
+fn open_with(
+    cache: Option<&InputCache>,
+    puzzle: PuzzleId,
+    filename: &Path,
+    embedded: Embedded,
+) -> Box<dyn BufRead> {
+    if let Some((_, contents)) = embedded
+        .iter()
+        .find(|(name, _)| Path::new(name) == filename)
+    {
+        return Box::new(contents.as_bytes());
+    }
+
+    let path = match cache {
+        Some(cache) if !filename.exists() => cache.path(puzzle, filename),
+        Some(cache) => {
+            // Best effort, a cache that can't be written to is only a slower cache
+            let _ = cache.store(puzzle, filename);
+            filename.to_path_buf()
+        }
+        None => filename.to_path_buf(),
+    };
+    let file = File::open(&path).unwrap_or_else(|e| panic!("{}: {}", filename.display(), e));
+    Box::new(io::BufReader::new(file))
+}
+
+/// Copies of input files under `<dir>/<year>/<day>/`, so two years' days with
+/// the same number never share a file
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        InputCache { dir: dir.into() }
+    }
+
+    /// The cache at `AOC_INPUT_CACHE`, if that is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os("AOC_INPUT_CACHE").map(Self::new)
+    }
+
+    /// Where `filename` of `puzzle` is kept, only its file name is used
+    pub fn path(&self, puzzle: PuzzleId, filename: impl AsRef<Path>) -> PathBuf {
+        let name = filename.as_ref().file_name().unwrap_or_default();
+        self.dir
+            .join(puzzle.year.to_string())
+            .join(puzzle.day.to_string())
+            .join(name)
+    }
+
+    /// Copy `filename` into the cache unless a copy is already there
+    pub fn store(&self, puzzle: PuzzleId, filename: impl AsRef<Path>) -> io::Result<()> {
+        let path = self.path(puzzle, &filename);
+        if path.exists() {
+            return Ok(());
+        }
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::copy(filename, path).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::extsort::SpillDir;
+
+    fn read(mut r: Box<dyn BufRead>) -> String {
+        let mut s = String::new();
+        r.read_to_string(&mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn keys_cached_files_by_year_and_day() {
+        let cache = InputCache::new("/cache");
+        assert_eq!(
+            cache.path(PuzzleId::new(2024, 6), "input"),
+            Path::new("/cache/2024/6/input")
+        );
+        assert_eq!(
+            cache.path(PuzzleId::new(2023, 6), "some/dir/test"),
+            Path::new("/cache/2023/6/test")
+        );
+    }
+
+    #[test]
+    fn embedded_files_come_first() {
+        let embedded: Embedded = &[("input", "from memory")];
+        let r = open_with(None, PuzzleId::new(2024, 1), Path::new("input"), embedded);
+        assert_eq!(read(r), "from memory");
+    }
+
+    #[test]
+    fn stores_and_falls_back_to_cached_copies() {
+        let dir = SpillDir::new().unwrap();
+        let cache = InputCache::new(dir.path().join("cache"));
+        let source = dir.path().join("input");
+        fs::write(&source, "1 2\n").unwrap();
+
+        let id = PuzzleId::new(2024, 1);
+        assert_eq!(read(open_with(Some(&cache), id, &source, &[])), "1 2\n");
+        assert_eq!(
+            fs::read_to_string(cache.path(id, "input")).unwrap(),
+            "1 2\n"
+        );
+
+        // Gone from the working directory, still there under its own puzzle only
+        fs::remove_file(&source).unwrap();
+        assert_eq!(read(open_with(Some(&cache), id, &source, &[])), "1 2\n");
+        assert!(!cache.path(PuzzleId::new(2023, 1), "input").exists());
+    }
+}
@@ -0,0 +1,17 @@
+pub mod alloc;
+pub mod arith;
+pub mod cancel;
+pub mod combinatorics;
+pub mod compare;
+pub mod extsort;
+pub mod fuzz;
+pub mod input;
+pub mod num;
+pub mod parse;
+pub mod progress;
+pub mod puzzle;
+pub mod repl;
+pub mod report;
+pub mod rng;
+pub mod submit;
+pub mod union_find;
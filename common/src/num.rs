@@ -0,0 +1,220 @@
+/// Greatest common divisor, `gcd(0, 0) == 0`
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, `lcm(0, n) == 0`
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    a / gcd(a, b) * b
+}
+
+/// Extended Euclid, returns `(g, x, y)` with `a * x + b * y == g` and `g >= 0`.
+/// Panics if `g` would be 2^63, which happens only when `a` and `b` are each
+/// 0 or `i64::MIN` and not both 0.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+
+    let g = i64::try_from(old_r).expect("gcd of 2^63 does not fit in an i64");
+    (g, old_x as i64, old_y as i64)
+}
+
+/// Inverse of `a` modulo `m` in `[0, m)`, if `a` and `m` are coprime
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m))
+}
+
+/// `(a * b) mod m` in `[0, m)`, computed in i128 so it can't overflow
+pub fn mul_mod(a: i64, b: i64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+/// `(a + b) mod m` in `[0, m)`, computed in i128 so it can't overflow
+pub fn add_mod(a: i64, b: i64, m: i64) -> i64 {
+    assert!(m > 0, "modulus must be positive");
+    (a as i128 + b as i128).rem_euclid(m as i128) as i64
+}
+
+/// Chinese remainder theorem over `(residue, modulus)` pairs.
+/// Moduli don't need to be coprime. Returns `(x, lcm)` with `x` in `[0, lcm)`,
+/// or `None` if the congruences are inconsistent or the lcm doesn't fit in an i64.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut acc = (0i64, 1i64);
+    for &(r2, m2) in congruences {
+        assert!(m2 > 0, "modulus must be positive");
+        let (r1, m1) = acc;
+        let r2 = r2.rem_euclid(m2);
+
+        let (g, p, _) = ext_gcd(m1, m2);
+        let diff = r2 as i128 - r1 as i128;
+        if diff % g as i128 != 0 {
+            return None;
+        }
+
+        let m2_g = (m2 / g) as i128;
+        let lcm = m1 as i128 * m2_g;
+        let lcm = i64::try_from(lcm).ok()?;
+        // m1 * k == diff (mod m2), k = (diff / g) * p (mod m2 / g)
+        let k = ((diff / g as i128) % m2_g * (p as i128 % m2_g)).rem_euclid(m2_g);
+        let x = (r1 as i128 + m1 as i128 * k).rem_euclid(lcm as i128);
+
+        acc = (x as i64, lcm);
+    }
+
+    Some(acc)
+}
+
+/// Exact integer solution of
+/// `m[0][0] * x + m[0][1] * y == rhs[0]`,
+/// `m[1][0] * x + m[1][1] * y == rhs[1]`.
+/// Returns `None` if the system is singular or has no integer solution.
+pub fn solve_2x2(m: [[i64; 2]; 2], rhs: [i64; 2]) -> Option<(i64, i64)> {
+    let [[a, b], [c, d]] = m.map(|r| r.map(|e| e as i128));
+    let [e, f] = rhs.map(|e| e as i128);
+
+    let det = a * d - b * c;
+    if det == 0 {
+        return None;
+    }
+
+    // Cramer's rule
+    let x_num = e * d - b * f;
+    let y_num = a * f - e * c;
+    if x_num % det != 0 || y_num % det != 0 {
+        return None;
+    }
+
+    let x = i64::try_from(x_num / det).ok()?;
+    let y = i64::try_from(y_num / det).ok()?;
+    Some((x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 5), 0);
+    }
+
+    #[test]
+    fn ext_gcd_satisfies_bezout() {
+        let values = [0, 1, -1, 2, -6, 35, -84, 1 << 40, i64::MAX, i64::MIN];
+        for a in values {
+            for b in values {
+                if (a == 0 || a == i64::MIN) && (b == 0 || b == i64::MIN) {
+                    continue;
+                }
+                let (g, x, y) = ext_gcd(a, b);
+                assert!(g >= 0, "ext_gcd({}, {})", a, b);
+                assert_eq!(a as i128 * x as i128 + b as i128 * y as i128, g as i128);
+                assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn ext_gcd_of_min_and_zero_panics() {
+        ext_gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn mod_inv_needs_coprime() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(mod_inv(5, 1), Some(0));
+    }
+
+    #[test]
+    fn mul_and_add_mod_do_not_overflow() {
+        assert_eq!(mul_mod(i64::MAX, i64::MAX, 1_000_000_007), 737_564_071);
+        assert_eq!(mul_mod(-3, 5, 7), 6);
+        assert_eq!(add_mod(i64::MAX, i64::MAX, 10), 4);
+        assert_eq!(add_mod(-1, 0, 10), 9);
+    }
+
+    #[test]
+    fn crt_coprime_and_negative_residues() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(-1, 3), (-2, 5)]), Some((8, 15)));
+        assert_eq!(crt(&[(-7, 4)]), Some((1, 4)));
+    }
+
+    #[test]
+    fn crt_non_coprime() {
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(3, 6), (3, 6)]), Some((3, 6)));
+        // Inconsistent: even modulo 4 but odd modulo 6
+        assert_eq!(crt(&[(2, 4), (3, 6)]), None);
+    }
+
+    #[test]
+    fn crt_lcm_overflow() {
+        let big = (1 << 40) + 15;
+        assert_eq!(crt(&[(0, big), (0, big + 2)]), None);
+    }
+
+    #[test]
+    fn crt_matches_brute_force() {
+        for m1 in 1..=12 {
+            for m2 in 1..=12 {
+                for r1 in -3..m1 {
+                    for r2 in -3..m2 {
+                        let lcm = lcm(m1 as u64, m2 as u64) as i64;
+                        let expected = (0..lcm)
+                            .find(|x| (x - r1).rem_euclid(m1) == 0 && (x - r2).rem_euclid(m2) == 0)
+                            .map(|x| (x, lcm));
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_2x2_cases() {
+        // 94a + 22b == 8400, 34a + 67b == 5400
+        assert_eq!(
+            solve_2x2([[94, 22], [34, 67]], [8400, 5400]),
+            Some((80, 40))
+        );
+        assert_eq!(solve_2x2([[1, 1], [1, -1]], [-4, 2]), Some((-1, -3)));
+        // Singular
+        assert_eq!(solve_2x2([[1, 2], [2, 4]], [3, 6]), None);
+        assert_eq!(solve_2x2([[0, 0], [0, 0]], [0, 0]), None);
+        // Only a fractional solution
+        assert_eq!(solve_2x2([[2, 0], [0, 2]], [1, 2]), None);
+    }
+}
@@ -0,0 +1,82 @@
+//! Structured errors for input parsers, so malformed input is reported instead of
+//! panicking somewhere in the middle of a parser
+
+use std::{error::Error, fmt, str::FromStr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line of the parsed lines, if known
+    pub line: Option<usize>,
+    pub msg: String,
+}
+
+impl ParseError {
+    pub fn new(msg: impl Into<String>) -> Self {
+        ParseError {
+            line: None,
+            msg: msg.into(),
+        }
+    }
+
+    /// Attach a line number unless the error already has one
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.msg),
+            None => write!(f, "{}", self.msg),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn number<T: FromStr>(s: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::new(format!("invalid number: {:?}", s)))
+}
+
+/// Parse every line with `f`, tagging errors with their line number
+pub fn lines_with<T>(
+    lines: impl IntoIterator<Item = String>,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, l)| f(&l).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+/// Non-empty rectangular grid made only of characters for which `allowed` holds
+pub fn grid(
+    lines: impl IntoIterator<Item = String>,
+    allowed: impl Fn(char) -> bool,
+) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid = lines_with(lines, |l| match l.chars().find(|c| !allowed(*c)) {
+        Some(c) => Err(ParseError::new(format!("unexpected character {:?}", c))),
+        None => Ok(l.chars().collect::<Vec<_>>()),
+    })?;
+
+    let width = grid.first().map_or(0, |r| r.len());
+    if width == 0 {
+        return Err(ParseError::new("empty grid"));
+    }
+    if let Some(y) = grid.iter().position(|r| r.len() != width) {
+        return Err(ParseError::new(format!("row length differs from {}", width)).at_line(y + 1));
+    }
+
+    Ok(grid)
+}
+
+/// Non-empty lines of `text`, the in-memory counterpart of the days' `read_lines`
+pub fn text_lines(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+}
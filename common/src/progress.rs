@@ -0,0 +1,227 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+/// Minimum time between redraws
+const REDRAW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Most ticks between clock reads
+const MAX_STRIDE: u64 = 1 << 16;
+
+/// Single line progress report on stderr for long loops. Counted loops show a
+/// percentage and ETA, unbounded ones only the count and rate. Redraws are throttled
+/// and nothing is printed at all when stderr is not a terminal.
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    done: u64,
+    start: Instant,
+    last_draw: Option<Instant>,
+    /// The clock is only read once `done` reaches `next_check`
+    next_check: u64,
+    stride: u64,
+    last_check: Instant,
+    enabled: bool,
+}
+
+impl Progress {
+    /// Progress for a loop of `total` units of work
+    pub fn counted(label: impl Into<String>, total: u64) -> Self {
+        Self::with_total(label.into(), Some(total))
+    }
+
+    /// Progress for a loop with no known end
+    pub fn unbounded(label: impl Into<String>) -> Self {
+        Self::with_total(label.into(), None)
+    }
+
+    fn with_total(label: String, total: Option<u64>) -> Self {
+        let start = Instant::now();
+        Progress {
+            label,
+            total,
+            done: 0,
+            start,
+            last_draw: None,
+            next_check: 0,
+            stride: 1,
+            last_check: start,
+            enabled: io::stderr().is_terminal(),
+        }
+    }
+
+    /// Record `n` more units of work, redrawing if enough time has passed.
+    /// Reading the clock costs more than a tight loop's body, so it is read every
+    /// `stride` units, with the stride doubling while the reads come much faster
+    /// than redraws are due and halving when they come too slowly.
+    pub fn inc(&mut self, n: u64) {
+        self.done += n;
+        if !self.enabled || self.done < self.next_check {
+            return;
+        }
+
+        let now = Instant::now();
+        let since_check = now.duration_since(self.last_check);
+        if since_check < REDRAW_INTERVAL / 16 {
+            self.stride = (self.stride * 2).min(MAX_STRIDE);
+        } else if since_check > REDRAW_INTERVAL / 4 {
+            self.stride = (self.stride / 2).max(1);
+        }
+        self.last_check = now;
+        self.next_check = self.done.saturating_add(self.stride);
+
+        // The first draw also waits an interval, so quick loops print nothing
+        let last = self.last_draw.unwrap_or(self.start);
+        if now.duration_since(last) >= REDRAW_INTERVAL {
+            self.last_draw = Some(now);
+            self.draw(now);
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.inc(1);
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done
+    }
+
+    /// Draw the final state and end the line. Also done on drop.
+    pub fn finish(&mut self) {
+        if self.enabled && self.last_draw.is_some() {
+            self.draw(Instant::now());
+            eprintln!();
+        }
+        self.enabled = false;
+    }
+
+    fn draw(&self, now: Instant) {
+        let line = render(
+            &self.label,
+            self.done,
+            self.total,
+            now.duration_since(self.start),
+        );
+        // Pad to overwrite a longer previous line
+        let mut stderr = io::stderr().lock();
+        let _ = write!(stderr, "\r{:<60}", line);
+        let _ = stderr.flush();
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Progress line text, e.g. `loops: 120/400 (30.0%) 55.2/s eta 5s`
+pub fn render(label: &str, done: u64, total: Option<u64>, elapsed: Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    let rate = if secs > 0.0 { done as f64 / secs } else { 0.0 };
+
+    match total {
+        Some(total) => {
+            let pct = if total > 0 {
+                done as f64 * 100.0 / total as f64
+            } else {
+                100.0
+            };
+            let eta = if rate > 0.0 {
+                format_duration(Duration::from_secs_f64(
+                    total.saturating_sub(done) as f64 / rate,
+                ))
+            } else {
+                "?".to_string()
+            };
+            format!(
+                "{}: {}/{} ({:.1}%) {:.1}/s eta {}",
+                label, done, total, pct, rate, eta
+            )
+        }
+        None => format!(
+            "{}: {} {:.1}/s elapsed {}",
+            label,
+            done,
+            rate,
+            format_duration(elapsed)
+        ),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, secs / 60 % 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counted() {
+        assert_eq!(
+            render("loops", 120, Some(400), Duration::from_secs(2)),
+            "loops: 120/400 (30.0%) 60.0/s eta 4s"
+        );
+        assert_eq!(
+            render("loops", 0, Some(400), Duration::ZERO),
+            "loops: 0/400 (0.0%) 0.0/s eta ?"
+        );
+    }
+
+    #[test]
+    fn zero_total() {
+        assert_eq!(
+            render("loops", 0, Some(0), Duration::from_secs(1)),
+            "loops: 0/0 (100.0%) 0.0/s eta ?"
+        );
+    }
+
+    #[test]
+    fn unbounded() {
+        assert_eq!(
+            render("nodes", 500, None, Duration::from_secs(10)),
+            "nodes: 500 50.0/s elapsed 10s"
+        );
+    }
+
+    #[test]
+    fn hour_range() {
+        assert_eq!(
+            render("loops", 1, Some(3601), Duration::from_secs(1)),
+            "loops: 1/3601 (0.0%) 1.0/s eta 1h00m"
+        );
+        assert_eq!(
+            render("nodes", 0, None, Duration::from_secs(3 * 3600 + 5 * 60 + 7)),
+            "nodes: 0 0.0/s elapsed 3h05m"
+        );
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_millis(999)), "0s");
+        assert_eq!(format_duration(Duration::from_secs(59)), "59s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m05s");
+        assert_eq!(format_duration(Duration::from_secs(3599)), "59m59s");
+        assert_eq!(format_duration(Duration::from_secs(36000)), "10h00m");
+    }
+
+    #[test]
+    fn reads_the_clock_less_often_in_tight_loops() {
+        let mut progress = Progress::unbounded("ticks");
+        progress.enabled = true;
+        for _ in 0..100_000 {
+            progress.tick();
+        }
+        assert!(progress.stride > 1, "stride {}", progress.stride);
+        assert!(progress.next_check > progress.done());
+    }
+}
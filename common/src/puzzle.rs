@@ -0,0 +1,134 @@
+use std::{fmt, str::FromStr};
+
+pub mod y2024;
+
+/// A puzzle is identified by its event year and day, so solutions from several
+/// years can sit side by side without their days colliding. Each day crate
+/// declares its own `PUZZLE` id and is listed under its year's module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: u8,
+}
+
+impl PuzzleId {
+    pub const fn new(year: u16, day: u8) -> Self {
+        PuzzleId { year, day }
+    }
+}
+
+/// A solved day in the registry
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub id: PuzzleId,
+    pub title: &'static str,
+    /// Crate directory relative to the repository root
+    pub dir: &'static str,
+}
+
+/// Every year's days, one list per year module
+pub const YEARS: &[&[Entry]] = &[y2024::DAYS];
+
+/// Every registered day, in year and day order
+pub fn registry() -> impl Iterator<Item = &'static Entry> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+/// The registered day with `id`
+pub fn find(id: PuzzleId) -> Option<&'static Entry> {
+    registry().find(|e| e.id == id)
+}
+
+/// Formats as `2024/6`, the same form [`PuzzleId::from_str`] accepts
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// Parse `<year>/<day>` or `<year>-<day>`, the day may have a `day` prefix
+impl FromStr for PuzzleId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s
+            .split_once(['/', '-'])
+            .ok_or_else(|| format!("expected <year>/<day>: {}", s))?;
+        let year = year
+            .parse()
+            .map_err(|_| format!("invalid year: {}", year))?;
+        let day = day
+            .strip_prefix("day")
+            .unwrap_or(day)
+            .parse()
+            .ok()
+            .filter(|d| (1..=25).contains(d))
+            .ok_or_else(|| format!("invalid day: {}", day))?;
+
+        Ok(PuzzleId { year, day })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_year_and_day() {
+        assert_eq!("2024/6".parse(), Ok(PuzzleId::new(2024, 6)));
+        assert_eq!("2024-25".parse(), Ok(PuzzleId::new(2024, 25)));
+        assert_eq!("2015/day1".parse(), Ok(PuzzleId::new(2015, 1)));
+        assert_eq!(PuzzleId::new(2024, 6).to_string(), "2024/6");
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!(
+            "2024".parse::<PuzzleId>(),
+            Err("expected <year>/<day>: 2024".to_string())
+        );
+        assert_eq!(
+            "twenty/6".parse::<PuzzleId>(),
+            Err("invalid year: twenty".to_string())
+        );
+        assert_eq!(
+            "2024/six".parse::<PuzzleId>(),
+            Err("invalid day: six".to_string())
+        );
+        assert!("2024/".parse::<PuzzleId>().is_err());
+        assert!("/6".parse::<PuzzleId>().is_err());
+        assert!("2024/6/1".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn rejects_days_outside_the_event() {
+        assert_eq!(
+            "2024/0".parse::<PuzzleId>(),
+            Err("invalid day: 0".to_string())
+        );
+        assert_eq!(
+            "2024/26".parse::<PuzzleId>(),
+            Err("invalid day: 26".to_string())
+        );
+        assert!("2024/256".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn registry_lists_2024() {
+        let ids = y2024::DAYS.iter().map(|e| e.id).collect::<Vec<_>>();
+        let expected = (1..=17).map(|d| PuzzleId::new(2024, d)).collect::<Vec<_>>();
+        assert_eq!(ids, expected);
+        assert!(
+            y2024::DAYS
+                .iter()
+                .all(|e| e.dir == format!("day{}", e.id.day))
+        );
+
+        assert_eq!(registry().count(), 17);
+        assert_eq!(
+            find(PuzzleId::new(2024, 6)).unwrap().title,
+            "Guard Gallivant"
+        );
+        assert_eq!(find(PuzzleId::new(2023, 6)), None);
+    }
+}
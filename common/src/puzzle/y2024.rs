@@ -0,0 +1,33 @@
+//! Advent of Code 2024
+
+use super::{Entry, PuzzleId};
+
+pub const YEAR: u16 = 2024;
+
+const fn day(day: u8, title: &'static str, dir: &'static str) -> Entry {
+    Entry {
+        id: PuzzleId::new(YEAR, day),
+        title,
+        dir,
+    }
+}
+
+pub const DAYS: &[Entry] = &[
+    day(1, "Historian Hysteria", "day1"),
+    day(2, "Red-Nosed Reports", "day2"),
+    day(3, "Mull It Over", "day3"),
+    day(4, "Ceres Search", "day4"),
+    day(5, "Print Queue", "day5"),
+    day(6, "Guard Gallivant", "day6"),
+    day(7, "Bridge Repair", "day7"),
+    day(8, "Resonant Collinearity", "day8"),
+    day(9, "Disk Fragmenter", "day9"),
+    day(10, "Hoof It", "day10"),
+    day(11, "Plutonian Pebbles", "day11"),
+    day(12, "Garden Groups", "day12"),
+    day(13, "Claw Contraption", "day13"),
+    day(14, "Restroom Redoubt", "day14"),
+    day(15, "Warehouse Woes", "day15"),
+    day(16, "Reindeer Maze", "day16"),
+    day(17, "Chronospatial Computer", "day17"),
+];
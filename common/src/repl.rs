@@ -0,0 +1,295 @@
+use std::io::{BufRead, Write};
+
+/// State that can be explored interactively with a [`Repl`]
+pub trait Simulation {
+    /// Advance by up to `n` steps, returning how many were taken.
+    /// Fewer than `n` means the simulation has ended.
+    fn step(&mut self, n: usize) -> usize;
+
+    fn render(&self) -> String;
+}
+
+type Action<S> = Box<dyn FnMut(&mut S, &[&str]) -> Result<String, String>>;
+
+struct Command<S> {
+    name: &'static str,
+    args: &'static str,
+    help: &'static str,
+    action: Action<S>,
+}
+
+/// Line based command loop around a simulation.
+/// Built in commands are `step [n]` (or just a number), `undo`, `show`, `history`,
+/// `help` and `done`. Any command that changes the state can be undone.
+pub struct Repl<S> {
+    state: S,
+    t: usize,
+    commands: Vec<Command<S>>,
+    history: Vec<String>,
+    undo_stack: Vec<(S, usize)>,
+}
+
+enum Outcome {
+    Changed(String),
+    Unchanged(String),
+    Done,
+}
+
+impl<S> Repl<S>
+where
+    S: Simulation + Clone,
+{
+    pub fn new(state: S) -> Self {
+        Repl {
+            state,
+            t: 0,
+            commands: Vec::new(),
+            history: Vec::new(),
+            undo_stack: Vec::new(),
+        }
+    }
+
+    /// Register a custom command. `args` is only used for `help` output.
+    /// The action gets the whitespace separated arguments and returns a message to print.
+    pub fn command(
+        mut self,
+        name: &'static str,
+        args: &'static str,
+        help: &'static str,
+        action: impl FnMut(&mut S, &[&str]) -> Result<String, String> + 'static,
+    ) -> Self {
+        self.commands.push(Command {
+            name,
+            args,
+            help,
+            action: Box::new(action),
+        });
+        self
+    }
+
+    /// Run until `done` or end of input, returning the final state and total steps taken
+    pub fn run(mut self, input: impl BufRead, mut output: impl Write) -> (S, usize) {
+        self.show(&mut output);
+        for line in input.lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let outcome = self.execute(line);
+            self.history.push(line.to_string());
+            match outcome {
+                Ok(Outcome::Done) => break,
+                Ok(Outcome::Changed(msg)) => {
+                    self.show(&mut output);
+                    if !msg.is_empty() {
+                        writeln!(output, "{}", msg).unwrap();
+                    }
+                }
+                Ok(Outcome::Unchanged(msg)) => {
+                    writeln!(output, "{}", msg).unwrap();
+                }
+                Err(e) => {
+                    writeln!(output, "error: {}", e).unwrap();
+                }
+            }
+        }
+
+        (self.state, self.t)
+    }
+
+    fn show(&self, output: &mut impl Write) {
+        write!(output, "{}", self.state.render()).unwrap();
+        writeln!(output, "t = {}\n", self.t).unwrap();
+    }
+
+    fn execute(&mut self, line: &str) -> Result<Outcome, String> {
+        let mut words = line.split_ascii_whitespace();
+        let name = words.next().unwrap();
+        let args = words.collect::<Vec<_>>();
+
+        // A bare number is shorthand for `step <n>`
+        if let Ok(n) = name.parse() {
+            return Ok(self.step(n));
+        }
+
+        match name {
+            "step" | "s" => {
+                let n = match args.first() {
+                    Some(n) => n
+                        .parse()
+                        .map_err(|_| format!("invalid step count: {}", n))?,
+                    None => 1,
+                };
+                Ok(self.step(n))
+            }
+            "undo" | "u" => {
+                let (state, t) = self.undo_stack.pop().ok_or("nothing to undo")?;
+                self.state = state;
+                self.t = t;
+                Ok(Outcome::Changed(String::new()))
+            }
+            "show" => Ok(Outcome::Changed(String::new())),
+            "history" => Ok(Outcome::Unchanged(
+                self.history
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| format!("{:>4}  {}", i + 1, l))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+            "help" | "?" => Ok(Outcome::Unchanged(self.help())),
+            "done" | "quit" | "q" => Ok(Outcome::Done),
+            _ => {
+                let command = self
+                    .commands
+                    .iter_mut()
+                    .find(|c| c.name == name)
+                    .ok_or_else(|| format!("unknown command: {} (try help)", name))?;
+
+                let mut state = self.state.clone();
+                let msg = (command.action)(&mut state, &args)?;
+                self.undo_stack
+                    .push((std::mem::replace(&mut self.state, state), self.t));
+                Ok(Outcome::Changed(msg))
+            }
+        }
+    }
+
+    fn step(&mut self, n: usize) -> Outcome {
+        self.undo_stack.push((self.state.clone(), self.t));
+        let taken = self.state.step(n);
+        self.t += taken;
+
+        if taken < n {
+            Outcome::Changed(format!("simulation ended after {} steps", taken))
+        } else {
+            Outcome::Changed(String::new())
+        }
+    }
+
+    fn help(&self) -> String {
+        let builtins = [
+            (
+                "step",
+                "[n]",
+                "advance n steps (default 1), a bare number also works",
+            ),
+            ("undo", "", "revert the last change"),
+            ("show", "", "render the current state"),
+            ("history", "", "list entered commands"),
+            ("help", "", "show this message"),
+            ("done", "", "leave the repl"),
+        ];
+
+        builtins
+            .into_iter()
+            .chain(self.commands.iter().map(|c| (c.name, c.args, c.help)))
+            .map(|(name, args, help)| format!("  {:<20} {}", format!("{} {}", name, args), help))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Counts up to `end`
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self, n: usize) -> usize {
+            let taken = n.min(self.end - self.value);
+            self.value += taken;
+            taken
+        }
+
+        fn render(&self) -> String {
+            format!("value {}\n", self.value)
+        }
+    }
+
+    fn run(repl: Repl<Counter>, input: &str) -> (Counter, usize, String) {
+        let mut output = Vec::new();
+        let (state, t) = repl.run(Cursor::new(input), &mut output);
+        (state, t, String::from_utf8(output).unwrap())
+    }
+
+    fn counter(end: usize) -> Repl<Counter> {
+        Repl::new(Counter { value: 0, end })
+    }
+
+    #[test]
+    fn steps_and_undoes() {
+        let (state, t, output) = run(counter(100), "step\n5\ns 3\nundo\nu\n");
+        assert_eq!((state.value, t), (1, 1));
+        assert_eq!(
+            output,
+            "value 0\nt = 0\n\n\
+             value 1\nt = 1\n\n\
+             value 6\nt = 6\n\n\
+             value 9\nt = 9\n\n\
+             value 6\nt = 6\n\n\
+             value 1\nt = 1\n\n"
+        );
+    }
+
+    #[test]
+    fn reports_end_of_simulation() {
+        let (state, t, output) = run(counter(4), "10\n");
+        assert_eq!((state.value, t), (4, 4));
+        assert!(output.ends_with("value 4\nt = 4\n\nsimulation ended after 4 steps\n"));
+    }
+
+    #[test]
+    fn stops_at_done() {
+        let (_, t, output) = run(counter(100), "2\n\ndone\n3\n");
+        assert_eq!(t, 2);
+        assert!(!output.contains("value 5"));
+    }
+
+    #[test]
+    fn reports_errors_and_keeps_going() {
+        let (_, t, output) = run(counter(100), "undo\nstep x\nfly\n1\n");
+        assert_eq!(t, 1);
+        assert!(output.contains("error: nothing to undo\n"));
+        assert!(output.contains("error: invalid step count: x\n"));
+        assert!(output.contains("error: unknown command: fly (try help)\n"));
+    }
+
+    #[test]
+    fn custom_commands_can_be_undone() {
+        let repl = counter(100).command("set", "<n>", "jump to n", |state, args| {
+            let n = args
+                .first()
+                .ok_or("missing value")?
+                .parse()
+                .map_err(|_| "invalid value".to_string())?;
+            state.value = n;
+            Ok(format!("set to {}", n))
+        });
+        let (state, t, output) = run(repl, "set 42\nset\nhelp\nundo\n");
+
+        assert_eq!((state.value, t), (0, 0));
+        assert!(output.contains("value 42\nt = 0\n\nset to 42\n"));
+        assert!(output.contains("error: missing value\n"));
+        assert!(output.contains("  set <n>              jump to n"));
+    }
+
+    #[test]
+    fn lists_history() {
+        let (_, _, output) = run(counter(100), "1\nshow\nhistory\n");
+        // The history command itself is recorded after it runs
+        assert!(output.ends_with("value 1\nt = 1\n\n   1  1\n   2  show\n"));
+    }
+}
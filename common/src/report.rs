@@ -0,0 +1,498 @@
+//! Self-contained HTML run report: answers, timings, check status and inline SVG
+//! renderings of grids. No external assets, so the file can be opened offline.
+//!
+//! Each day binary run with `--report <file>` merges its record into `<file>`, so
+//! running every day with the same path builds one report covering all of them.
+//! The records are kept as text inside the HTML and read back on the next merge.
+
+use std::{
+    fmt::{Display, Write as _},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    puzzle::PuzzleId,
+    submit::{GuessStore, Verdict},
+};
+
+/// Largest width or height of a rendered grid in pixels
+const MAX_GRID_PX: usize = 640;
+
+/// Encloses the record data in a report. `<` is escaped in the data, so it can't
+/// close the element early.
+const DATA_START: &str = "<script type=\"text/plain\" id=\"records\">\n";
+const DATA_END: &str = "</script>\n";
+
+pub struct PartResult {
+    pub answer: String,
+    pub time: Duration,
+    /// Whether the answer matched the known answer, `None` if there is none
+    pub verified: Option<bool>,
+}
+
+impl PartResult {
+    pub fn new(answer: impl Display, time: Duration) -> Self {
+        PartResult {
+            answer: answer.to_string(),
+            time,
+            verified: None,
+        }
+    }
+}
+
+/// Everything the report shows for one day
+pub struct DayRecord {
+    pub id: PuzzleId,
+    pub title: String,
+    pub parts: Vec<PartResult>,
+    /// Whether the example input gave the expected answers, `None` if not checked
+    pub example: Option<bool>,
+    /// Captioned SVG renderings, see [`grid_svg`]
+    pub grids: Vec<(String, String)>,
+}
+
+impl DayRecord {
+    pub fn new(id: PuzzleId, title: impl Into<String>) -> Self {
+        DayRecord {
+            id,
+            title: title.into(),
+            parts: Vec::new(),
+            example: None,
+            grids: Vec::new(),
+        }
+    }
+
+    pub fn part(mut self, part: PartResult) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    pub fn grid(mut self, caption: impl Into<String>, svg: String) -> Self {
+        self.grids.push((caption.into(), svg));
+        self
+    }
+
+    /// Whether the example input gave the expected answers
+    pub fn example(mut self, passed: bool) -> Self {
+        self.example = Some(passed);
+        self
+    }
+
+    /// Mark each part right or wrong from the verdicts `store` has for its answer.
+    /// Parts the store knows nothing about stay unverified.
+    pub fn verify(mut self, store: &GuessStore) -> Self {
+        for (i, part) in self.parts.iter_mut().enumerate() {
+            if let Some(verdict) = store.verdict(self.id, i as u8 + 1, &part.answer) {
+                part.verified = Some(verdict == Verdict::Right);
+            }
+        }
+        self
+    }
+
+    /// Lines of the record data, with fields separated by tabs
+    fn to_data(&self) -> String {
+        let mut data = format!(
+            "day\t{}\t{}\t{}\n",
+            self.id,
+            escape_field(&self.title),
+            status_token(self.example)
+        );
+        for part in &self.parts {
+            data += &format!(
+                "part\t{}\t{}\t{}\n",
+                escape_field(&part.answer),
+                part.time.as_nanos(),
+                status_token(part.verified)
+            );
+        }
+        for (caption, svg) in &self.grids {
+            data += &format!("grid\t{}\t{}\n", escape_field(caption), escape_field(svg));
+        }
+
+        data
+    }
+
+    /// Records written by [`DayRecord::to_data`]
+    fn from_data(data: &str) -> Option<Vec<DayRecord>> {
+        let mut records: Vec<DayRecord> = Vec::new();
+        for line in data.lines() {
+            let fields = line.split('\t').map(unescape_field).collect::<Vec<_>>();
+            match fields.as_slice() {
+                [kind, id, title, example] if kind == "day" => records.push(DayRecord {
+                    example: status_from_token(example)?,
+                    ..DayRecord::new(id.parse().ok()?, title)
+                }),
+                [kind, answer, nanos, verified] if kind == "part" => {
+                    records.last_mut()?.parts.push(PartResult {
+                        answer: answer.clone(),
+                        time: Duration::from_nanos(nanos.parse().ok()?),
+                        verified: status_from_token(verified)?,
+                    })
+                }
+                [kind, caption, svg] if kind == "grid" => records
+                    .last_mut()?
+                    .grids
+                    .push((caption.clone(), svg.clone())),
+                _ => return None,
+            }
+        }
+
+        Some(records)
+    }
+}
+
+/// Run `f`, also returning how long it took
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parse `--report <file>` or `--report=<file>`
+pub fn path_from_args(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--report" {
+            return args.next().map(PathBuf::from);
+        } else if let Some(path) = arg.strip_prefix("--report=") {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
+pub fn write(path: impl AsRef<Path>, records: &[DayRecord]) -> io::Result<()> {
+    fs::write(path, render_html(records))
+}
+
+/// Records in the report at `path`, none if it doesn't exist yet
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<DayRecord>> {
+    let path = path.as_ref();
+    let html = match fs::read_to_string(path) {
+        Ok(html) => html,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let invalid = || {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: not a run report", path.display()),
+        )
+    };
+    let start = html.find(DATA_START).ok_or_else(invalid)? + DATA_START.len();
+    let len = html[start..].find(DATA_END).ok_or_else(invalid)?;
+    DayRecord::from_data(&html[start..start + len]).ok_or_else(invalid)
+}
+
+/// Add `record` to the report at `path`, replacing an earlier record for the same
+/// puzzle. Days are kept in puzzle order.
+pub fn merge(path: impl AsRef<Path>, record: DayRecord) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut records = read(path)?;
+    records.retain(|r| r.id != record.id);
+    records.push(record);
+    records.sort_by_key(|r| r.id);
+    write(path, &records)
+}
+
+pub fn render_html(records: &[DayRecord]) -> String {
+    let mut html = String::new();
+    html.push_str(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Advent of Code run report</title>\n<style>\n",
+        "body { font-family: sans-serif; margin: 2em; background: #fafafa; }\n",
+        "table { border-collapse: collapse; margin-bottom: 2em; }\n",
+        "th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }\n",
+        "td.num { text-align: right; font-family: monospace; }\n",
+        ".pass { color: #080; } .fail { color: #c00; } .unknown { color: #888; }\n",
+        "figure { display: inline-block; margin: 0 1em 1em 0; }\n",
+        "</style>\n</head>\n<body>\n<h1>Advent of Code run report</h1>\n",
+    ));
+
+    // Summary of all days first, then one section per day with its grids
+    html.push_str(
+        "<table>\n<tr><th>Puzzle</th><th>Part</th><th>Answer</th><th>Time</th>\
+         <th>Verified</th><th>Example</th></tr>\n",
+    );
+    for record in records {
+        for (i, part) in record.parts.iter().enumerate() {
+            writeln!(
+                html,
+                "<tr><td><a href=\"#{}-{}\">{}</a></td><td>{}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}</td><td>{}</td><td>{}</td></tr>",
+                record.id.year,
+                record.id.day,
+                record.id,
+                i + 1,
+                escape(&part.answer),
+                format_time(part.time),
+                status(part.verified),
+                status(record.example),
+            )
+            .unwrap();
+        }
+    }
+    html.push_str("</table>\n");
+
+    for record in records {
+        writeln!(
+            html,
+            "<h2 id=\"{}-{}\">{} day {}: {}</h2>",
+            record.id.year,
+            record.id.day,
+            record.id.year,
+            record.id.day,
+            escape(&record.title)
+        )
+        .unwrap();
+        let total = record.parts.iter().map(|p| p.time).sum();
+        writeln!(html, "<p>Total time {}</p>", format_time(total)).unwrap();
+        for (caption, svg) in &record.grids {
+            writeln!(
+                html,
+                "<figure>\n{}\n<figcaption>{}</figcaption>\n</figure>",
+                svg,
+                escape(caption)
+            )
+            .unwrap();
+        }
+    }
+
+    html.push_str(DATA_START);
+    for record in records {
+        html.push_str(&record.to_data());
+    }
+    html.push_str(DATA_END);
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Inline SVG of a character grid, one square per cell. `color` gives the fill for
+/// a cell, or `None` to leave it as background.
+pub fn grid_svg<R>(grid: &[R], color: impl Fn(char) -> Option<&'static str>) -> String
+where
+    R: AsRef<[char]>,
+{
+    let height = grid.len();
+    let width = grid.iter().map(|r| r.as_ref().len()).max().unwrap_or(0);
+    let cell = (MAX_GRID_PX / width.max(height).max(1)).max(1);
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+         viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+        width * cell,
+        height * cell,
+        width,
+        height
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect width=\"{}\" height=\"{}\" fill=\"#fff\"/>",
+        width, height
+    )
+    .unwrap();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &c) in row.as_ref().iter().enumerate() {
+            if let Some(fill) = color(c) {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                    x, y, fill
+                )
+                .unwrap();
+            }
+        }
+    }
+    svg.push_str("</svg>");
+
+    svg
+}
+
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Escape backslashes, tabs, newlines and `<` so a field fits on one data line
+fn escape_field(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '<' => escaped.push_str("\\<"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn unescape_field(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+
+    unescaped
+}
+
+fn status_token(status: Option<bool>) -> &'static str {
+    match status {
+        Some(true) => "pass",
+        Some(false) => "fail",
+        None => "-",
+    }
+}
+
+fn status_from_token(token: &str) -> Option<Option<bool>> {
+    match token {
+        "pass" => Some(Some(true)),
+        "fail" => Some(Some(false)),
+        "-" => Some(None),
+        _ => None,
+    }
+}
+
+fn status(status: Option<bool>) -> &'static str {
+    match status {
+        Some(true) => "<span class=\"pass\">pass</span>",
+        Some(false) => "<span class=\"fail\">fail</span>",
+        None => "<span class=\"unknown\">&ndash;</span>",
+    }
+}
+
+fn format_time(d: Duration) -> String {
+    if d.as_secs() > 0 {
+        format!("{:.2} s", d.as_secs_f64())
+    } else if d.as_millis() > 0 {
+        format!("{:.2} ms", d.as_secs_f64() * 1e3)
+    } else {
+        format!("{:.1} µs", d.as_secs_f64() * 1e6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("report-{}-{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn record(day: u8, answer: &str) -> DayRecord {
+        DayRecord::new(PuzzleId::new(2024, day), format!("Day <{}>", day))
+            .part(PartResult::new(answer, Duration::from_micros(1500)))
+            .part(PartResult::new("a\tb\\c\nd", Duration::from_secs(2)))
+            .grid(
+                "tiny",
+                grid_svg(&[vec!['#', '.']], |c| (c == '#').then_some("#444")),
+            )
+            .example(day.is_multiple_of(2))
+    }
+
+    #[test]
+    fn records_survive_a_round_trip() {
+        let path = temp_path("round-trip");
+        write(&path, &[record(3, "42"), record(4, "</script>")]).unwrap();
+
+        let records = read(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].to_data(), record(4, "</script>").to_data());
+        assert_eq!(records[1].parts[0].answer, "</script>");
+        assert_eq!(records[0].parts[1].answer, "a\tb\\c\nd");
+        assert_eq!(records[0].parts[0].time, Duration::from_micros(1500));
+        assert_eq!(records[0].example, Some(false));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn merge_replaces_the_same_day_and_keeps_order() {
+        let path = temp_path("merge");
+        merge(&path, record(5, "1")).unwrap();
+        merge(&path, record(2, "2")).unwrap();
+        merge(&path, record(5, "3")).unwrap();
+
+        let records = read(&path).unwrap();
+        let answers = records
+            .iter()
+            .map(|r| (r.id.day, r.parts[0].answer.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(answers, [(2, "2"), (5, "3")]);
+
+        let html = fs::read_to_string(&path).unwrap();
+        assert_eq!(html.matches("<h2 id=").count(), 2);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn refuses_to_merge_into_other_files() {
+        let path = temp_path("other");
+        fs::write(&path, "<html>not a report</html>").unwrap();
+        assert_eq!(
+            merge(&path, record(1, "1")).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn verifies_parts_from_the_guess_store() {
+        let path = temp_path("guesses");
+        fs::write(
+            &path,
+            "wait 0\nguess 2024/1 1 right 11\nguess 2024/1 2 high 40\nguess 2024/2 1 low 5\n",
+        )
+        .unwrap();
+        let store = GuessStore::open(&path).unwrap();
+
+        let verified = |day, answers: [&str; 2]| {
+            let record = answers
+                .iter()
+                .fold(DayRecord::new(PuzzleId::new(2024, day), "title"), |r, a| {
+                    r.part(PartResult::new(a, Duration::ZERO))
+                });
+            record
+                .verify(&store)
+                .parts
+                .iter()
+                .map(|p| p.verified)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(verified(1, ["11", "40"]), [Some(true), Some(false)]);
+        assert_eq!(verified(1, ["12", "31"]), [Some(false), None]);
+        assert_eq!(verified(2, ["6", "1"]), [None, None]);
+        fs::remove_file(path).unwrap();
+    }
+}
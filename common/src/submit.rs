@@ -0,0 +1,608 @@
+//! Answer submission over plain HTTP. Guesses are remembered in a local store so a
+//! wrong answer is never posted twice, answers outside a known too high/too low
+//! bound are refused, and the cooldown the server asks for is respected without
+//! contacting it.
+//!
+//! There is no TLS support, so the real site has to be reached through a local
+//! HTTP proxy. The base URL comes from `AOC_BASE_URL`, which has no default for
+//! that reason, the session token from `AOC_SESSION` and the store path from
+//! `AOC_STATE`.
+//!
+//! There is no single `aoc` binary to hang an `aoc submit <day> <part>` command
+//! on, so each day binary takes `--submit <part>` instead: `day3 --submit 2`
+//! solves as usual and posts the part 2 answer. See [`Submission`].
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    io::{self, Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::puzzle::PuzzleId;
+
+const DEFAULT_STATE: &str = ".aoc-submissions";
+const USER_AGENT: &str = "aoc24-submit";
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Answered too recently, with the time left to wait
+    Wait(Duration),
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Config(String),
+    Io(io::Error),
+    /// Still cooling down from an earlier answer, with the time left
+    Cooldown(Duration),
+    /// This exact answer was submitted before and got this verdict
+    AlreadyGuessed(Verdict),
+    /// The answer lies beyond an earlier answer that got this verdict
+    OutOfBounds(Verdict, String),
+    /// The server replied with something other than 200 OK
+    Http(String),
+    /// The response did not contain a verdict we recognize
+    UnexpectedResponse(String),
+    /// The answer is empty or contains whitespace, which the store can't hold
+    InvalidAnswer(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wait(left) => write!(f, "wait {}s", left.as_secs()),
+        }
+    }
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmitError::Config(msg) => write!(f, "{}", msg),
+            SubmitError::Io(e) => write!(f, "{}", e),
+            SubmitError::Cooldown(left) => {
+                write!(f, "cooling down, {}s left to wait", left.as_secs())
+            }
+            SubmitError::AlreadyGuessed(verdict) => {
+                write!(f, "already submitted, it was {}", verdict)
+            }
+            SubmitError::OutOfBounds(verdict, answer) => {
+                write!(f, "not submitted, {} was already {}", answer, verdict)
+            }
+            SubmitError::Http(status) => write!(f, "server replied {}", status),
+            SubmitError::UnexpectedResponse(body) => write!(f, "unexpected response: {}", body),
+            SubmitError::InvalidAnswer(answer) => {
+                write!(f, "invalid answer {:?}, expected one word", answer)
+            }
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+impl From<io::Error> for SubmitError {
+    fn from(e: io::Error) -> Self {
+        SubmitError::Io(e)
+    }
+}
+
+impl Verdict {
+    /// Read the verdict from the text of a response page
+    pub fn parse(body: &str) -> Option<Verdict> {
+        if body.contains("That's the right answer") {
+            Some(Verdict::Right)
+        } else if body.contains("answer too recently") {
+            Some(Verdict::Wait(wait_left(body).unwrap_or_default()))
+        } else if body.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if body.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if body.contains("not the right answer") {
+            Some(Verdict::Wrong)
+        } else {
+            None
+        }
+    }
+
+    fn token(&self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "high",
+            Verdict::TooLow => "low",
+            Verdict::Wait(_) => "wait",
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Verdict> {
+        match token {
+            "right" => Some(Verdict::Right),
+            "wrong" => Some(Verdict::Wrong),
+            "high" => Some(Verdict::TooHigh),
+            "low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+}
+
+/// `You have 1m 30s left to wait` as a duration
+fn wait_left(body: &str) -> Option<Duration> {
+    let before = &body[..body.find(" left to wait")?];
+    let secs = before
+        .rsplit(' ')
+        .map_while(|w| match w.split_at_checked(w.len().checked_sub(1)?)? {
+            (n, "m") => Some(n.parse::<u64>().ok()?.saturating_mul(60)),
+            (n, "s") => n.parse::<u64>().ok(),
+            _ => None,
+        })
+        .fold(0u64, u64::saturating_add);
+
+    Some(Duration::from_secs(secs))
+}
+
+/// The cooldown after a wrong answer, `please wait one minute` or `please wait 5 minutes`
+fn wrong_cooldown(body: &str) -> Option<Duration> {
+    let body = body.to_lowercase();
+    let rest = &body[body.find("please wait ")? + "please wait ".len()..];
+    let (count, rest) = rest.split_once(' ')?;
+    let count: u64 = match count {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+
+    rest.starts_with("minute")
+        .then(|| Duration::from_secs(count.saturating_mul(60)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Guess {
+    puzzle: PuzzleId,
+    part: u8,
+    verdict: Verdict,
+    answer: String,
+}
+
+/// Submitted answers and the end of the current cooldown, kept in a text file
+#[derive(Debug)]
+pub struct GuessStore {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+    /// Unix time before which nothing may be submitted
+    wait_until: u64,
+}
+
+impl GuessStore {
+    /// Load the store at `path`, a missing file is an empty store
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, SubmitError> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut store = GuessStore {
+            path,
+            guesses: Vec::new(),
+            wait_until: 0,
+        };
+        for (i, line) in text.lines().enumerate() {
+            store.load_line(line).ok_or_else(|| {
+                SubmitError::Config(format!(
+                    "{}: bad line {}: {:?}",
+                    store.path.display(),
+                    i + 1,
+                    line
+                ))
+            })?;
+        }
+
+        Ok(store)
+    }
+
+    fn load_line(&mut self, line: &str) -> Option<()> {
+        let mut words = line.split(' ');
+        match words.next()? {
+            "wait" => self.wait_until = words.next()?.parse().ok()?,
+            "guess" => self.guesses.push(Guess {
+                puzzle: words.next()?.parse().ok()?,
+                part: words.next()?.parse().ok()?,
+                verdict: Verdict::from_token(words.next()?)?,
+                answer: words.next()?.to_string(),
+            }),
+            _ => return None,
+        }
+
+        Some(())
+    }
+
+    /// The store at `AOC_STATE`, or `~/.aoc-submissions` if that isn't set
+    pub fn from_env() -> Result<Self, SubmitError> {
+        let path = std::env::var_os("AOC_STATE")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(DEFAULT_STATE)))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_STATE));
+        Self::open(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// What is known about `answer`: the verdict it got, or `Wrong` if another
+    /// answer to the part was right. `None` if neither was submitted.
+    pub fn verdict(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Option<Verdict> {
+        let answer = answer.trim();
+        let mut guesses = self
+            .guesses
+            .iter()
+            .filter(|g| g.puzzle == puzzle && g.part == part);
+        guesses.find_map(|g| {
+            if g.answer == answer {
+                Some(g.verdict)
+            } else if g.verdict == Verdict::Right {
+                Some(Verdict::Wrong)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Why `answer` must not be submitted now, if there is a reason
+    pub fn check(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+        now: SystemTime,
+    ) -> Result<(), SubmitError> {
+        check_answer(answer)?;
+        let now = unix_secs(now);
+        if now < self.wait_until {
+            return Err(SubmitError::Cooldown(Duration::from_secs(
+                self.wait_until - now,
+            )));
+        }
+
+        let guesses = self
+            .guesses
+            .iter()
+            .filter(|g| g.puzzle == puzzle && g.part == part);
+        let number = answer.parse::<i128>().ok();
+        for guess in guesses {
+            if guess.answer == answer || guess.verdict == Verdict::Right {
+                return Err(SubmitError::AlreadyGuessed(guess.verdict));
+            }
+            if let Some(number) = number
+                && let Ok(bound) = guess.answer.parse::<i128>()
+                && ((guess.verdict == Verdict::TooHigh && number >= bound)
+                    || (guess.verdict == Verdict::TooLow && number <= bound))
+            {
+                return Err(SubmitError::OutOfBounds(
+                    guess.verdict,
+                    guess.answer.clone(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remember the verdict on `answer` and wait `cooldown` before the next one
+    pub fn record(
+        &mut self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+        verdict: Verdict,
+        cooldown: Option<Duration>,
+        now: SystemTime,
+    ) -> Result<(), SubmitError> {
+        check_answer(answer)?;
+        if let Some(cooldown) = cooldown {
+            self.wait_until = unix_secs(now).saturating_add(cooldown.as_secs());
+        }
+        if !matches!(verdict, Verdict::Wait(_)) {
+            self.guesses.push(Guess {
+                puzzle,
+                part,
+                verdict,
+                answer: answer.to_string(),
+            });
+        }
+
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), SubmitError> {
+        let mut text = format!("wait {}\n", self.wait_until);
+        for g in &self.guesses {
+            text += &format!(
+                "guess {} {} {} {}\n",
+                g.puzzle,
+                g.part,
+                g.verdict.token(),
+                g.answer
+            );
+        }
+
+        if let Some(dir) = self.path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+/// Answers are stored as one space separated word, so they can't be empty or
+/// contain whitespace
+fn check_answer(answer: &str) -> Result<(), SubmitError> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(SubmitError::InvalidAnswer(answer.to_string()));
+    }
+    Ok(())
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+/// `http://host[:port][/prefix]`
+#[derive(Clone, Debug)]
+struct BaseUrl {
+    host: String,
+    port: u16,
+    prefix: String,
+}
+
+impl BaseUrl {
+    fn parse(url: &str) -> Result<Self, SubmitError> {
+        if url.starts_with("https://") {
+            return Err(SubmitError::Config(format!(
+                "{}: TLS is not supported, use an http:// URL (e.g. a local proxy)",
+                url
+            )));
+        }
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| SubmitError::Config(format!("not an http:// URL: {}", url)))?;
+
+        let (authority, prefix) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| SubmitError::Config(format!("invalid port: {}", port)))?,
+            ),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(SubmitError::Config(format!("no host in URL: {}", url)));
+        }
+
+        Ok(BaseUrl {
+            host: host.to_string(),
+            port,
+            prefix: prefix.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+pub struct Client {
+    base: BaseUrl,
+    session: String,
+    store: GuessStore,
+}
+
+impl Client {
+    pub fn new(
+        base_url: &str,
+        session: impl Into<String>,
+        store: GuessStore,
+    ) -> Result<Self, SubmitError> {
+        Ok(Client {
+            base: BaseUrl::parse(base_url)?,
+            session: session.into(),
+            store,
+        })
+    }
+
+    /// Client configured by `AOC_BASE_URL`, `AOC_SESSION` and `AOC_STATE`
+    pub fn from_env() -> Result<Self, SubmitError> {
+        let base_url = std::env::var("AOC_BASE_URL").map_err(|_| {
+            SubmitError::Config(
+                "AOC_BASE_URL is not set. TLS is not supported, so point it at an http:// \
+                 proxy for https://adventofcode.com"
+                    .to_string(),
+            )
+        })?;
+        let session = std::env::var("AOC_SESSION")
+            .map_err(|_| SubmitError::Config("AOC_SESSION is not set".to_string()))?;
+
+        Self::new(&base_url, session, GuessStore::from_env()?)
+    }
+
+    pub fn store(&self) -> &GuessStore {
+        &self.store
+    }
+
+    /// Post `answer` unless the store says it must not be, and record the verdict
+    pub fn submit(
+        &mut self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, SubmitError> {
+        let answer = answer.trim();
+        self.store.check(puzzle, part, answer, SystemTime::now())?;
+
+        let body = self.post(
+            &format!(
+                "{}/{}/day/{}/answer",
+                self.base.prefix, puzzle.year, puzzle.day
+            ),
+            &format!("level={}&answer={}", part, form_encode(answer)),
+        )?;
+        let verdict = Verdict::parse(&body)
+            .ok_or_else(|| SubmitError::UnexpectedResponse(page_text(&body)))?;
+        let cooldown = match verdict {
+            Verdict::Wait(left) => Some(left),
+            _ => wrong_cooldown(&body),
+        };
+
+        self.store
+            .record(puzzle, part, answer, verdict, cooldown, SystemTime::now())?;
+        Ok(verdict)
+    }
+
+    /// POST a form and return the body of a 200 response
+    fn post(&self, path: &str, form: &str) -> Result<String, SubmitError> {
+        let mut stream = TcpStream::connect((self.base.host.as_str(), self.base.port))?;
+        stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+        stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+
+        // HTTP/1.0 so the body is neither chunked nor kept alive
+        write!(
+            stream,
+            "POST {} HTTP/1.0\r\n\
+             Host: {}\r\n\
+             User-Agent: {}\r\n\
+             Cookie: session={}\r\n\
+             Content-Type: application/x-www-form-urlencoded\r\n\
+             Content-Length: {}\r\n\
+             \r\n\
+             {}",
+            path,
+            self.base.host,
+            USER_AGENT,
+            self.session,
+            form.len(),
+            form
+        )?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        let response = String::from_utf8_lossy(&response);
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head.lines().next().unwrap_or_default();
+        if status.split(' ').nth(1) != Some("200") {
+            return Err(SubmitError::Http(status.to_string()));
+        }
+
+        Ok(body.to_string())
+    }
+}
+
+fn form_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Body with tags stripped and whitespace collapsed, short enough for an error
+fn page_text(body: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    text.chars().take(200).collect()
+}
+
+/// Parse `--submit <part>` or `--submit=<part>`, the part must be 1 or 2
+pub fn part_from_args(args: impl IntoIterator<Item = String>) -> Result<Option<u8>, SubmitError> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let part = if arg == "--submit" {
+            args.next()
+        } else if let Some(part) = arg.strip_prefix("--submit=") {
+            Some(part.to_string())
+        } else {
+            continue;
+        };
+
+        let Some(part) = part else {
+            return Err(SubmitError::Config(
+                "missing --submit value, expected part 1 or 2".to_string(),
+            ));
+        };
+        return part
+            .parse()
+            .ok()
+            .filter(|p| (1..=2).contains(p))
+            .map(Some)
+            .ok_or_else(|| {
+                SubmitError::Config(format!("invalid --submit {:?}, expected part 1 or 2", part))
+            });
+    }
+
+    Ok(None)
+}
+
+/// What a day binary was asked to submit, see [`Submission::answer`]
+pub struct Submission {
+    puzzle: PuzzleId,
+    part: Option<u8>,
+}
+
+impl Submission {
+    /// Exits if `--submit` was given without a valid part
+    pub fn from_args(puzzle: PuzzleId) -> Self {
+        match part_from_args(std::env::args()) {
+            Ok(part) => Submission { puzzle, part },
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// Submit `answer` if `--submit` asked for this part, printing the verdict.
+    /// Exits if the answer could not be submitted.
+    pub fn answer(&self, part: u8, answer: impl Display) {
+        if self.part != Some(part) {
+            return;
+        }
+
+        let result =
+            Client::from_env().and_then(|mut c| c.submit(self.puzzle, part, &answer.to_string()));
+        match result {
+            Ok(verdict) => println!("{} part {}: {}", self.puzzle, part, verdict),
+            Err(e) => {
+                eprintln!("{} part {}: {}", self.puzzle, part, e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
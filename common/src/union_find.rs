@@ -0,0 +1,227 @@
+/// Disjoint-set forest with path compression and union by rank
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint sets
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // Point everything on the path straight at the root
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+
+        root
+    }
+
+    /// Merge the sets containing `a` and `b`, false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = if self.rank[a] < self.rank[b] {
+            (b, a)
+        } else {
+            (a, b)
+        };
+        if self.rank[root] == self.rank[child] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.components -= 1;
+
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the set containing `x`
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+}
+
+/// Connected components of a grid, ids are dense and assigned in row-major order
+pub struct Components {
+    /// Component id of each cell, same shape as the grid
+    pub labels: Vec<Vec<usize>>,
+    /// Cell count per component id
+    pub sizes: Vec<usize>,
+    /// First cell (x, y) of each component in row-major order
+    pub representatives: Vec<(usize, usize)>,
+}
+
+/// Label 4-connected components of `grid`, where neighbouring cells are joined
+/// when `connected` holds for them. Rows may have different lengths.
+pub fn label_grid<R, T>(grid: &[R], connected: impl Fn(&T, &T) -> bool) -> Components
+where
+    R: AsRef<[T]>,
+{
+    let offsets = grid
+        .iter()
+        .scan(0, |offset, row| {
+            let start = *offset;
+            *offset += row.as_ref().len();
+            Some(start)
+        })
+        .collect::<Vec<_>>();
+    let cell_count = grid.iter().map(|r| r.as_ref().len()).sum();
+
+    // Single scan, joining each cell with its left and upper neighbours
+    let mut uf = UnionFind::new(cell_count);
+    for (y, row) in grid.iter().enumerate() {
+        let row = row.as_ref();
+        for (x, cell) in row.iter().enumerate() {
+            let idx = offsets[y] + x;
+            if x > 0 && connected(&row[x - 1], cell) {
+                uf.union(idx - 1, idx);
+            }
+            if y > 0
+                && let Some(up) = grid[y - 1].as_ref().get(x)
+                && connected(up, cell)
+            {
+                uf.union(offsets[y - 1] + x, idx);
+            }
+        }
+    }
+
+    // Relabel roots to dense ids
+    let mut root_ids = vec![usize::MAX; cell_count];
+    let mut sizes = Vec::with_capacity(uf.component_count());
+    let mut representatives = Vec::with_capacity(uf.component_count());
+    let labels = grid
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            (0..row.as_ref().len())
+                .map(|x| {
+                    let root = uf.find(offsets[y] + x);
+                    if root_ids[root] == usize::MAX {
+                        root_ids[root] = sizes.len();
+                        sizes.push(uf.size(root));
+                        representatives.push((x, y));
+                    }
+                    root_ids[root]
+                })
+                .collect()
+        })
+        .collect();
+
+    Components {
+        labels,
+        sizes,
+        representatives,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_tracks_sets_and_sizes() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.len(), 6);
+        assert_eq!(uf.component_count(), 6);
+
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+
+        assert!(uf.same(0, 3));
+        assert!(!uf.same(0, 4));
+        assert_eq!(uf.size(2), 4);
+        assert_eq!(uf.size(5), 1);
+        assert_eq!(uf.component_count(), 3);
+    }
+
+    #[test]
+    fn empty() {
+        let uf = UnionFind::new(0);
+        assert!(uf.is_empty());
+        assert_eq!(uf.component_count(), 0);
+
+        let components = label_grid::<Vec<char>, char>(&[], |a, b| a == b);
+        assert!(components.labels.is_empty());
+        assert!(components.sizes.is_empty());
+    }
+
+    #[test]
+    fn labels_regions_in_row_major_order() {
+        let grid = ["AAB", "ABB", "CCB"]
+            .map(|r| r.chars().collect::<Vec<_>>())
+            .to_vec();
+        let components = label_grid(&grid, |a, b| a == b);
+
+        assert_eq!(
+            components.labels,
+            vec![vec![0, 0, 1], vec![0, 1, 1], vec![2, 2, 1]]
+        );
+        assert_eq!(components.sizes, vec![3, 4, 2]);
+        assert_eq!(components.representatives, vec![(0, 0), (2, 0), (0, 2)]);
+    }
+
+    #[test]
+    fn joins_only_4_neighbours() {
+        // The diagonal X cells are separate components
+        let grid = ["XO", "OX"].map(|r| r.chars().collect::<Vec<_>>()).to_vec();
+        let components = label_grid(&grid, |a, b| a == b);
+        assert_eq!(components.sizes, vec![1; 4]);
+
+        // A U shape is joined through its bottom row
+        let grid = ["X.X", "X.X", "XXX"]
+            .map(|r| r.chars().collect::<Vec<_>>())
+            .to_vec();
+        let components = label_grid(&grid, |a, b| a == b);
+        assert_eq!(components.sizes, vec![7, 2]);
+        assert_eq!(components.labels[0], vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let grid = vec![vec![1, 1, 1], vec![1], vec![2, 1]];
+        let components = label_grid(&grid, |a, b| a == b);
+        assert_eq!(components.labels, vec![vec![0, 0, 0], vec![0], vec![1, 2]]);
+        assert_eq!(components.sizes, vec![4, 1, 1]);
+    }
+}
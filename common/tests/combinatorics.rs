@@ -0,0 +1,83 @@
+use common::combinatorics::{combinations, pairs, pairwise, permutations, product};
+
+#[test]
+fn pairs_are_unordered_and_complete() {
+    let items = [1, 2, 3, 4];
+    let got: Vec<_> = pairs(&items).map(|(&a, &b)| (a, b)).collect();
+    assert_eq!(got, [(1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)]);
+}
+
+#[test]
+fn pairs_size_hint_is_exact() {
+    let items = [0; 7];
+    let mut it = pairs(&items);
+    for remaining in (0..=21).rev() {
+        assert_eq!(it.size_hint(), (remaining, Some(remaining)));
+        it.next();
+    }
+    assert!(it.next().is_none());
+}
+
+#[test]
+fn pairs_of_short_slices() {
+    assert_eq!(pairs::<u8>(&[]).count(), 0);
+    assert_eq!(pairs(&[1]).count(), 0);
+    assert_eq!(pairs(&[1, 2]).count(), 1);
+}
+
+#[test]
+fn combinations_match_binomial() {
+    let items: Vec<_> = (0..6).collect();
+    let got: Vec<_> = combinations::<_, 3>(&items)
+        .map(|c| c.map(|&e| e))
+        .collect();
+    assert_eq!(got.len(), 20);
+    assert_eq!(got.first(), Some(&[0, 1, 2]));
+    assert_eq!(got.last(), Some(&[3, 4, 5]));
+    assert!(got.iter().all(|c| c[0] < c[1] && c[1] < c[2]));
+}
+
+#[test]
+fn combinations_edge_cases() {
+    assert_eq!(combinations::<_, 0>(&[1, 2]).count(), 1);
+    assert_eq!(combinations::<_, 3>(&[1, 2]).count(), 0);
+    assert_eq!(combinations::<_, 2>(&[1, 2]).count(), 1);
+}
+
+#[test]
+fn permutations_are_lexicographic() {
+    let items = ['a', 'b', 'c'];
+    let got: Vec<String> = permutations::<_, 2>(&items)
+        .map(|p| p.into_iter().collect())
+        .collect();
+    assert_eq!(got, ["ab", "ac", "ba", "bc", "ca", "cb"]);
+}
+
+#[test]
+fn permutations_count() {
+    let items: Vec<_> = (0..5).collect();
+    assert_eq!(permutations::<_, 5>(&items).count(), 120);
+    assert_eq!(permutations::<_, 3>(&items).count(), 60);
+    assert_eq!(permutations::<_, 0>(&items).count(), 1);
+    assert_eq!(permutations::<_, 6>(&items).count(), 0);
+}
+
+#[test]
+fn product_covers_grid() {
+    let got: Vec<_> = product(&[1, 2], &['x', 'y', 'z'])
+        .map(|(&a, &b)| (a, b))
+        .collect();
+    assert_eq!(
+        got,
+        [(1, 'x'), (1, 'y'), (1, 'z'), (2, 'x'), (2, 'y'), (2, 'z')]
+    );
+    assert_eq!(product::<u8, u8>(&[1], &[]).count(), 0);
+    assert_eq!(product::<u8, u8>(&[], &[1]).count(), 0);
+}
+
+#[test]
+fn pairwise_windows() {
+    let got: Vec<_> = pairwise(&[1, 2, 3]).map(|(&a, &b)| (a, b)).collect();
+    assert_eq!(got, [(1, 2), (2, 3)]);
+    assert_eq!(pairwise(&[1]).len(), 0);
+}
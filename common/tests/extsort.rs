@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+
+use common::{
+    extsort::{ExternalSorter, SpillDir, SpillingCounter},
+    rng::Rng,
+};
+
+fn random_values(seed: u64, n: usize, max: u64) -> Vec<u64> {
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| rng.below(max)).collect()
+}
+
+fn external_sort(values: &[u64], chunk_len: usize) -> (usize, Vec<u64>) {
+    let mut sorter = ExternalSorter::new(SpillDir::new().unwrap(), chunk_len);
+    for &v in values {
+        sorter.push(v).unwrap();
+    }
+    let runs = sorter.finish().unwrap();
+    let sorted = runs.merge().unwrap().collect::<Result<_, _>>().unwrap();
+    (runs.run_count(), sorted)
+}
+
+#[test]
+fn sorts_across_runs() {
+    for chunk_len in [1, 3, 7] {
+        let values = random_values(chunk_len as u64, 100, 20);
+        let (runs, sorted) = external_sort(&values, chunk_len);
+
+        let mut expected = values.clone();
+        expected.sort();
+        assert_eq!(runs, values.len().div_ceil(chunk_len));
+        assert_eq!(sorted, expected, "chunk length {}", chunk_len);
+    }
+}
+
+#[test]
+fn merges_in_several_passes() {
+    // With one value per run this needs two passes to get down to 128 runs
+    let values = random_values(1, 128 * 128 + 5, 1000);
+    let (runs, sorted) = external_sort(&values, 1);
+
+    let mut expected = values.clone();
+    expected.sort();
+    assert!(runs <= 128, "{} runs left after merging", runs);
+    assert_eq!(sorted, expected);
+}
+
+#[test]
+fn empty_input() {
+    let (runs, sorted) = external_sort(&[], 3);
+    assert_eq!(runs, 0);
+    assert!(sorted.is_empty());
+}
+
+#[test]
+fn counts_sum_entries_from_every_run() {
+    for max_keys in [1, 3] {
+        let values = random_values(max_keys as u64, 200, 15);
+        let mut counter = SpillingCounter::new(SpillDir::new().unwrap(), max_keys);
+        for &v in &values {
+            counter.add(v).unwrap();
+        }
+        let counts = counter.finish().unwrap();
+        let got = counts
+            .iter()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        let expected = values.iter().fold(BTreeMap::new(), |mut acc, &v| {
+            *acc.entry(v).or_insert(0) += 1;
+            acc
+        });
+        assert_eq!(got, expected.into_iter().collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn removes_run_files_on_drop() {
+    let mut sorter = ExternalSorter::new(SpillDir::new().unwrap(), 2);
+    for v in [5_u64, 3, 9, 1, 4] {
+        sorter.push(v).unwrap();
+    }
+    let runs = sorter.finish().unwrap();
+    let dir = runs.dir().to_path_buf();
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+    drop(runs);
+    assert!(!dir.exists());
+}
@@ -0,0 +1,232 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use common::{
+    puzzle::PuzzleId,
+    submit::{Client, GuessStore, SubmitError, Verdict, part_from_args},
+};
+
+const PUZZLE: PuzzleId = PuzzleId::new(2024, 1);
+
+const RIGHT: &str =
+    "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+    Please wait one minute before trying again.</p></article>";
+const WRONG: &str = "<article><p>That's not the right answer. If you're stuck, \
+    please wait 5 minutes before trying again.</p></article>";
+const WAIT: &str =
+    "<article><p>You gave an answer too recently. You have 1m 30s left to wait.</p></article>";
+
+/// Local stand-in server answering one connection per body, returning the requests it got
+fn serve(bodies: &[&'static str]) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/aoc", listener.local_addr().unwrap());
+    let bodies = bodies.to_vec();
+
+    let server = thread::spawn(move || {
+        bodies
+            .into_iter()
+            .map(|body| {
+                let (mut stream, _) = listener.accept().unwrap();
+                stream
+                    .set_read_timeout(Some(Duration::from_secs(5)))
+                    .unwrap();
+                let request = read_request(&mut stream);
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+                request
+            })
+            .collect()
+    });
+
+    (url, server)
+}
+
+fn read_request(stream: &mut impl Read) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    loop {
+        let n = stream.read(&mut buf).unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some((head, body)) = text.split_once("\r\n\r\n") {
+            let len = head
+                .lines()
+                .find_map(|l| l.strip_prefix("Content-Length: "))
+                .map_or(0, |l| l.parse().unwrap());
+            if body.len() >= len {
+                return text.into_owned();
+            }
+        }
+        assert!(n > 0, "connection closed mid-request");
+    }
+}
+
+fn store_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("submit-{}-{}", std::process::id(), name));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn posts_answer_with_session() {
+    let (url, server) = serve(&[RIGHT]);
+    let store = GuessStore::open(store_path("right")).unwrap();
+    let mut client = Client::new(&url, "abc123", store).unwrap();
+
+    assert_eq!(client.submit(PUZZLE, 2, "42\n").unwrap(), Verdict::Right);
+
+    let requests = server.join().unwrap();
+    let request = &requests[0];
+    assert!(request.starts_with("POST /aoc/2024/day/1/answer HTTP/1.0\r\n"));
+    assert!(request.contains("\r\nCookie: session=abc123\r\n"));
+    assert!(request.ends_with("\r\n\r\nlevel=2&answer=42"));
+
+    // A solved part is not submitted again
+    assert!(matches!(
+        client.submit(PUZZLE, 2, "43"),
+        Err(SubmitError::AlreadyGuessed(Verdict::Right))
+    ));
+}
+
+#[test]
+fn remembers_wrong_guesses_and_bounds() {
+    let path = store_path("wrong");
+    let (url, server) = serve(&[TOO_HIGH, WRONG]);
+    let mut client = Client::new(&url, "s", GuessStore::open(&path).unwrap()).unwrap();
+
+    assert_eq!(client.submit(PUZZLE, 1, "100").unwrap(), Verdict::TooHigh);
+    // Still cooling down from the wrong answer
+    assert!(matches!(
+        client.submit(PUZZLE, 1, "50"),
+        Err(SubmitError::Cooldown(left)) if left.as_secs() > 50
+    ));
+
+    // Clear the cooldown by hand, keeping the guesses
+    let text = fs::read_to_string(&path).unwrap();
+    let guesses = text.lines().skip(1).collect::<Vec<_>>().join("\n");
+    fs::write(&path, format!("wait 0\n{}\n", guesses)).unwrap();
+    let mut client = Client::new(&url, "s", GuessStore::open(&path).unwrap()).unwrap();
+
+    assert!(matches!(
+        client.submit(PUZZLE, 1, "100"),
+        Err(SubmitError::AlreadyGuessed(Verdict::TooHigh))
+    ));
+    assert!(matches!(
+        client.submit(PUZZLE, 1, "150"),
+        Err(SubmitError::OutOfBounds(Verdict::TooHigh, _))
+    ));
+    // The bound is per part
+    assert_eq!(client.submit(PUZZLE, 2, "150").unwrap(), Verdict::Wrong);
+
+    assert_eq!(server.join().unwrap().len(), 2);
+}
+
+#[test]
+fn waits_when_asked_to() {
+    let (url, server) = serve(&[WAIT]);
+    let store = GuessStore::open(store_path("wait")).unwrap();
+    let mut client = Client::new(&url, "s", store).unwrap();
+
+    assert_eq!(
+        client.submit(PUZZLE, 1, "7").unwrap(),
+        Verdict::Wait(Duration::from_secs(90))
+    );
+    // Not recorded as a guess, but nothing goes out until the wait is over
+    assert!(matches!(
+        client.submit(PUZZLE, 1, "7"),
+        Err(SubmitError::Cooldown(_))
+    ));
+
+    assert_eq!(server.join().unwrap().len(), 1);
+}
+
+#[test]
+fn survives_an_absurd_wait() {
+    let mut store = GuessStore::open(store_path("absurd")).unwrap();
+    let wait = Verdict::parse(
+        "You gave an answer too recently. You have 999999999999999999m left to wait.",
+    )
+    .unwrap();
+    assert_eq!(wait, Verdict::Wait(Duration::from_secs(u64::MAX)));
+
+    let now = SystemTime::now();
+    store
+        .record(PUZZLE, 1, "7", wait, Some(Duration::MAX), now)
+        .unwrap();
+    assert!(matches!(
+        store.check(PUZZLE, 1, "7", now),
+        Err(SubmitError::Cooldown(_))
+    ));
+}
+
+#[test]
+fn parses_the_submit_flag() {
+    let parse = |args: &[&str]| part_from_args(args.iter().map(|a| a.to_string()));
+
+    assert_eq!(parse(&["day1", "input"]).unwrap(), None);
+    assert_eq!(parse(&["day1", "--submit", "2"]).unwrap(), Some(2));
+    assert_eq!(parse(&["day1", "--submit=1", "input"]).unwrap(), Some(1));
+    for bad in [
+        &["day1", "--submit"][..],
+        &["--submit", "3"],
+        &["--submit=x"],
+    ] {
+        assert!(
+            matches!(parse(bad), Err(SubmitError::Config(_))),
+            "{:?}",
+            bad
+        );
+    }
+}
+
+#[test]
+fn rejects_https_and_unknown_pages() {
+    let store = GuessStore::open(store_path("https")).unwrap();
+    assert!(matches!(
+        Client::new("https://adventofcode.com", "s", store),
+        Err(SubmitError::Config(_))
+    ));
+
+    let (url, server) = serve(&["<p>Please log in.</p>"]);
+    let store = GuessStore::open(store_path("unknown")).unwrap();
+    let mut client = Client::new(&url, "s", store).unwrap();
+    assert!(matches!(
+        client.submit(PUZZLE, 1, "1"),
+        Err(SubmitError::UnexpectedResponse(text)) if text == "Please log in."
+    ));
+    server.join().unwrap();
+}
+
+#[test]
+fn rejects_answers_the_store_cannot_hold() {
+    let path = store_path("whitespace");
+    // Nothing listens here, the answers are refused before connecting
+    let mut client =
+        Client::new("http://127.0.0.1:9", "s", GuessStore::open(&path).unwrap()).unwrap();
+
+    for answer in ["1 2", "a\tb", "  ", ""] {
+        assert!(matches!(
+            client.submit(PUZZLE, 1, answer),
+            Err(SubmitError::InvalidAnswer(_))
+        ));
+    }
+
+    let mut store = GuessStore::open(&path).unwrap();
+    assert!(matches!(
+        store.record(PUZZLE, 1, "1 2", Verdict::Wrong, None, SystemTime::now()),
+        Err(SubmitError::InvalidAnswer(_))
+    ));
+    assert!(!path.exists());
+}
@@ -0,0 +1,847 @@
+//! Day 1: Historian Hysteria
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    io::{self, BufRead},
+    path::Path,
+};
+
+use common::{
+    arith,
+    extsort::{Counts, ExternalSorter, SortedRuns, SpillDir, SpillingCounter},
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+    rng::Rng,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 1);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// Parse a `left   right` pair of location ids
+pub fn line_to_locs(line: &str) -> Result<(u64, u64), ParseError> {
+    let ids = line_to_ids(line)?;
+    let [left, right] = ids[..] else {
+        return Err(ParseError::new(format!(
+            "expected two location ids, found {}",
+            ids.len()
+        )));
+    };
+
+    Ok((left, right))
+}
+
+/// Parse a row of any number of whitespace-separated location ids
+pub fn line_to_ids(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split_ascii_whitespace().map(parse::number).collect()
+}
+
+/// Parse rows of location ids into their columns, one list per group of historians.
+/// Every row must have as many ids as the first.
+pub fn lines_to_columns(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<u64>>, ParseError> {
+    let mut columns: Vec<Vec<u64>> = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let ids = line_to_ids(&line).map_err(|e| e.at_line(i + 1))?;
+        if i == 0 {
+            columns = vec![Vec::new(); ids.len()];
+        }
+        if ids.len() != columns.len() {
+            return Err(ParseError::new(format!(
+                "expected {} location ids, found {}",
+                columns.len(),
+                ids.len()
+            ))
+            .at_line(i + 1));
+        }
+        for (column, id) in columns.iter_mut().zip(ids) {
+            column.push(id);
+        }
+    }
+
+    if columns.is_empty() {
+        return Err(ParseError::new("no location ids"));
+    }
+    Ok(columns)
+}
+
+/// Parse the puzzle's two lists. Rows with any other number of ids are an error,
+/// [`lines_to_columns`] reads more than two columns.
+pub fn lines_to_locs(lines: impl Iterator<Item = String>) -> Result<Vec<(u64, u64)>, ParseError> {
+    parse::lines_with(lines, line_to_locs)
+}
+
+/// [`lines_to_locs`] on the text of an input file, so also two columns only
+pub fn parse(text: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    lines_to_locs(parse::text_lines(text))
+}
+
+/// One pair of the sorted lists with the input lines its ids came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pairing {
+    pub left: u64,
+    /// 1-based line of `left`, not counting blank lines
+    pub left_line: usize,
+    pub right: u64,
+    pub right_line: usize,
+    pub distance: u64,
+}
+
+/// Pair the smallest left id with the smallest right id and so on. Equal ids keep
+/// their input order.
+pub fn pairings(locs: &[(u64, u64)]) -> Vec<Pairing> {
+    let sorted_lines = |id: fn(&(u64, u64)) -> u64| {
+        let mut lines = (0..locs.len()).collect::<Vec<_>>();
+        lines.sort_by_key(|&i| id(&locs[i]));
+        lines
+    };
+    let left_lines = sorted_lines(|(l, _)| *l);
+    let right_lines = sorted_lines(|(_, r)| *r);
+
+    left_lines
+        .into_iter()
+        .zip(right_lines)
+        .map(|(li, ri)| {
+            let (left, right) = (locs[li].0, locs[ri].1);
+            Pairing {
+                left,
+                left_line: li + 1,
+                right,
+                right_line: ri + 1,
+                distance: left.abs_diff(right),
+            }
+        })
+        .collect()
+}
+
+/// The `n` pairings with the largest distance, largest first
+pub fn largest_contributors(pairings: &[Pairing], n: usize) -> Vec<Pairing> {
+    let mut ranked = pairings.to_vec();
+    ranked.sort_by(|a, b| {
+        b.distance
+            .cmp(&a.distance)
+            .then(a.left_line.cmp(&b.left_line))
+    });
+    ranked.truncate(n);
+    ranked
+}
+
+/// Pairings as a text table, with each distance's share of `total`
+pub fn pairing_table(pairings: &[Pairing], total: u64) -> String {
+    let mut table = format!(
+        "{:>6} {:>10} {:>6} {:>10} {:>10} {:>7}\n",
+        "line", "left", "line", "right", "distance", "share"
+    );
+    for p in pairings {
+        let share = if total == 0 {
+            0.0
+        } else {
+            p.distance as f64 * 100.0 / total as f64
+        };
+        table += &format!(
+            "{:>6} {:>10} {:>6} {:>10} {:>10} {:>6.2}%\n",
+            p.left_line, p.left, p.right_line, p.right, p.distance, share
+        );
+    }
+
+    table
+}
+
+/// Part 1: total distance between the sorted left and right lists
+pub fn dist_sum(locs: &[(u64, u64)]) -> u64 {
+    let (left, right) = split_locs(locs);
+    column_dist(&left, &right)
+}
+
+/// Part 2: each left id times the number of times it appears in the right list
+pub fn similarity_sum(locs: &[(u64, u64)]) -> u64 {
+    let (left, right) = split_locs(locs);
+    column_similarity(&left, &right)
+}
+
+fn split_locs(locs: &[(u64, u64)]) -> (Vec<u64>, Vec<u64>) {
+    locs.iter().copied().unzip()
+}
+
+/// [`dist_sum`] of two columns
+pub fn column_dist(a: &[u64], b: &[u64]) -> u64 {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+
+    a.sort();
+    b.sort();
+
+    arith::sum(a.into_iter().zip(b).map(|(x, y)| x.abs_diff(y)))
+}
+
+/// [`similarity_sum`] of two columns
+pub fn column_similarity(a: &[u64], b: &[u64]) -> u64 {
+    let b_freq = b.iter().fold(HashMap::new(), |mut acc, r| {
+        acc.insert(r, acc.get(&r).unwrap_or(&0) + 1_u64);
+        acc
+    });
+
+    arith::sum(
+        a.iter()
+            .map(|l| arith::mul(*l, *b_freq.get(l).unwrap_or(&0))),
+    )
+}
+
+/// A way of scoring how similar the left and right lists are
+pub trait SimilarityMetric {
+    /// Name used to select the metric on the command line
+    fn name(&self) -> &str;
+    fn score(&self, left: &[u64], right: &[u64]) -> Score;
+}
+
+/// A metric's value. Counts stay integers so large totals are exact, an `f64`
+/// only holds integers exactly up to 2^53.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Score {
+    Count(u64),
+    Ratio(f64),
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Count(n) => write!(f, "{}", n),
+            Score::Ratio(r) => write!(f, "{}", r),
+        }
+    }
+}
+
+/// The puzzle's score: each left id times its count in the right list
+pub struct CountWeighted;
+
+/// Number of ids the lists have in common, counting repeats
+pub struct MultisetIntersection;
+
+/// Distinct ids in both lists over distinct ids in either
+pub struct Jaccard;
+
+/// Common ids weighted by their value, `min(left count, right count) * id` summed
+pub struct WeightedOverlap;
+
+impl SimilarityMetric for CountWeighted {
+    fn name(&self) -> &str {
+        "count-weighted"
+    }
+
+    fn score(&self, left: &[u64], right: &[u64]) -> Score {
+        Score::Count(column_similarity(left, right))
+    }
+}
+
+impl SimilarityMetric for MultisetIntersection {
+    fn name(&self) -> &str {
+        "intersection"
+    }
+
+    fn score(&self, left: &[u64], right: &[u64]) -> Score {
+        let right_freq = id_counts(right);
+        let common = id_counts(left)
+            .into_iter()
+            .map(|(id, n)| n.min(*right_freq.get(&id).unwrap_or(&0)));
+        Score::Count(arith::sum(common))
+    }
+}
+
+impl SimilarityMetric for Jaccard {
+    fn name(&self) -> &str {
+        "jaccard"
+    }
+
+    fn score(&self, left: &[u64], right: &[u64]) -> Score {
+        let (left_freq, right_freq) = (id_counts(left), id_counts(right));
+        let both = left_freq
+            .keys()
+            .filter(|id| right_freq.contains_key(id))
+            .count();
+        let either = left_freq.len() + right_freq.len() - both;
+
+        if either == 0 {
+            Score::Ratio(0.0)
+        } else {
+            Score::Ratio(both as f64 / either as f64)
+        }
+    }
+}
+
+impl SimilarityMetric for WeightedOverlap {
+    fn name(&self) -> &str {
+        "weighted-overlap"
+    }
+
+    fn score(&self, left: &[u64], right: &[u64]) -> Score {
+        let right_freq = id_counts(right);
+        let common = id_counts(left)
+            .into_iter()
+            .map(|(id, n)| arith::mul(id, n.min(*right_freq.get(&id).unwrap_or(&0))));
+        Score::Count(arith::sum(common))
+    }
+}
+
+fn id_counts(ids: &[u64]) -> HashMap<u64, u64> {
+    ids.iter().fold(HashMap::new(), |mut acc, id| {
+        *acc.entry(*id).or_default() += 1;
+        acc
+    })
+}
+
+/// Metrics selectable by name, the built-ins plus any registered ones
+pub struct MetricRegistry {
+    metrics: Vec<Box<dyn SimilarityMetric>>,
+}
+
+impl MetricRegistry {
+    pub fn new() -> Self {
+        MetricRegistry {
+            metrics: vec![
+                Box::new(CountWeighted),
+                Box::new(MultisetIntersection),
+                Box::new(Jaccard),
+                Box::new(WeightedOverlap),
+            ],
+        }
+    }
+
+    /// Add a metric, replacing any with the same name
+    pub fn register(&mut self, metric: impl SimilarityMetric + 'static) {
+        self.metrics.retain(|m| m.name() != metric.name());
+        self.metrics.push(Box::new(metric));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn SimilarityMetric> {
+        self.metrics
+            .iter()
+            .find(|m| m.name() == name)
+            .map(|m| m.as_ref())
+    }
+
+    pub fn metrics(&self) -> impl Iterator<Item = &dyn SimilarityMetric> {
+        self.metrics.iter().map(|m| m.as_ref())
+    }
+}
+
+impl Default for MetricRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Scores of every pair of columns, indexed `[a][b]`
+pub struct ScoreMatrix {
+    pub dist: Vec<Vec<u64>>,
+    pub similarity: Vec<Vec<u64>>,
+}
+
+pub fn score_matrix(columns: &[Vec<u64>]) -> ScoreMatrix {
+    let matrix = |score: fn(&[u64], &[u64]) -> u64| {
+        columns
+            .iter()
+            .map(|a| columns.iter().map(|b| score(a, b)).collect())
+            .collect()
+    };
+
+    ScoreMatrix {
+        dist: matrix(column_dist),
+        similarity: matrix(column_similarity),
+    }
+}
+
+/// A square matrix as a text table with columns labelled from 1
+pub fn matrix_table(matrix: &[Vec<u64>]) -> String {
+    let width = matrix
+        .iter()
+        .flatten()
+        .map(|v| v.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(matrix.len().to_string().len());
+
+    let mut table = format!("{:>w$}", "", w = matrix.len().to_string().len());
+    for b in 1..=matrix.len() {
+        table += &format!(" {:>w$}", b, w = width);
+    }
+    table += "\n";
+    for (a, row) in matrix.iter().enumerate() {
+        table += &format!("{:>w$}", a + 1, w = matrix.len().to_string().len());
+        for v in row {
+            table += &format!(" {:>w$}", v, w = width);
+        }
+        table += "\n";
+    }
+
+    table
+}
+
+/// Both lists sorted on disk, for inputs too large to hold in memory
+pub struct ExternalLists {
+    left: SortedRuns<u64>,
+    right: SortedRuns<u64>,
+    right_counts: Counts,
+}
+
+impl ExternalLists {
+    /// Read the lists keeping at most `chunk_len` ids per column in memory. Run
+    /// files go to the system temporary directory and are removed on drop.
+    pub fn from_lines(lines: impl Iterator<Item = String>, chunk_len: usize) -> io::Result<Self> {
+        let mut left = ExternalSorter::new(SpillDir::new()?, chunk_len);
+        let mut right = ExternalSorter::new(SpillDir::new()?, chunk_len);
+        let mut right_counts = SpillingCounter::new(SpillDir::new()?, chunk_len);
+
+        for (i, line) in lines.enumerate() {
+            let (l, r) = line_to_locs(&line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.at_line(i + 1)))?;
+            left.push(l)?;
+            right.push(r)?;
+            right_counts.add(r)?;
+        }
+
+        Ok(ExternalLists {
+            left: left.finish()?,
+            right: right.finish()?,
+            right_counts: right_counts.finish()?,
+        })
+    }
+
+    /// [`dist_sum`] over the merged sorted runs
+    pub fn dist_sum(&self) -> io::Result<u64> {
+        let mut sum = 0;
+        for (l, r) in self.left.merge()?.zip(self.right.merge()?) {
+            sum = arith::add(sum, l?.abs_diff(r?));
+        }
+
+        Ok(sum)
+    }
+
+    /// [`similarity_sum`] as a merge join of the sorted left list with the right counts
+    pub fn similarity_sum(&self) -> io::Result<u64> {
+        let mut counts = self.right_counts.iter()?;
+        let mut count = counts.next().transpose()?;
+
+        let mut sum = 0;
+        for l in self.left.merge()? {
+            let l = l?;
+            while let Some((r, _)) = count
+                && r < l
+            {
+                count = counts.next().transpose()?;
+            }
+            if let Some((r, n)) = count
+                && r == l
+            {
+                sum = arith::add(sum, arith::mul(l, n));
+            }
+        }
+
+        Ok(sum)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Both lists with the part 1 and part 2 totals available as ids are inserted
+/// and removed.
+///
+/// The similarity total changes by the id times its count on the other side, an
+/// O(1) update from the frequency maps. The distance total pairs the i-th
+/// smallest ids of each side, up to the length of the shorter one, and one
+/// insert can shift every later pair, so it is not kept as a running total.
+/// Instead the ids live in an order-statistic tree with O(log n)
+/// updates, and [`IncrementalLists::dist_sum`] reads the total off the tree in
+/// O(log n) per point where the sorted lists cross.
+#[derive(Clone, Debug, Default)]
+pub struct IncrementalLists {
+    ids: IdTree,
+    left_freq: HashMap<u64, u64>,
+    right_freq: HashMap<u64, u64>,
+    similarity: u64,
+}
+
+impl IncrementalLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_locs(locs: &[(u64, u64)]) -> Self {
+        let mut lists = Self::new();
+        for &(l, r) in locs {
+            lists.insert(Side::Left, l);
+            lists.insert(Side::Right, r);
+        }
+        lists
+    }
+
+    pub fn insert(&mut self, side: Side, id: u64) {
+        let other_count = *self.freq(side.other()).get(&id).unwrap_or(&0);
+        let is_new = other_count == 0 && !self.freq(side).contains_key(&id);
+        self.ids.add(side, id, 1, is_new);
+
+        self.similarity = arith::add(self.similarity, arith::mul(id, other_count));
+        *self.freq_mut(side).entry(id).or_default() += 1;
+    }
+
+    /// Remove one occurrence of `id`, returning whether there was one
+    pub fn remove(&mut self, side: Side, id: u64) -> bool {
+        let Some(count) = self.freq_mut(side).get_mut(&id) else {
+            return false;
+        };
+        *count -= 1;
+        if *count == 0 {
+            self.freq_mut(side).remove(&id);
+        }
+
+        let other_count = *self.freq(side.other()).get(&id).unwrap_or(&0);
+        let is_gone = other_count == 0 && !self.freq(side).contains_key(&id);
+        self.ids.add(side, id, -1, is_gone);
+        self.similarity = arith::sub(self.similarity, arith::mul(id, other_count));
+
+        true
+    }
+
+    pub fn len(&self, side: Side) -> usize {
+        self.ids.len(side) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len(Side::Left) == 0 && self.len(Side::Right) == 0
+    }
+
+    /// The `rank`-th smallest id on `side`
+    pub fn select(&self, side: Side, rank: usize) -> Option<u64> {
+        self.ids.select(side, rank as u64)
+    }
+
+    /// [`dist_sum`] of the current lists
+    pub fn dist_sum(&self) -> u64 {
+        self.ids.dist()
+    }
+
+    /// [`similarity_sum`] of the current lists
+    pub fn similarity_sum(&self) -> u64 {
+        self.similarity
+    }
+
+    fn freq(&self, side: Side) -> &HashMap<u64, u64> {
+        match side {
+            Side::Left => &self.left_freq,
+            Side::Right => &self.right_freq,
+        }
+    }
+
+    fn freq_mut(&mut self, side: Side) -> &mut HashMap<u64, u64> {
+        match side {
+            Side::Left => &mut self.left_freq,
+            Side::Right => &mut self.right_freq,
+        }
+    }
+}
+
+/// Treap of the distinct ids of both sides, each with its count per side.
+///
+/// Walking the ids in order, the left count minus the right count of the ids so
+/// far is D(x) = C_L(x) - C_R(x), the difference of the two counting functions.
+/// For lists of equal length the sorted distance is the area between them,
+/// the integral of |D|, and every subtree keeps its lowest and highest running
+/// D so the next point where D changes sign is found in O(log n).
+#[derive(Clone, Debug, Default)]
+struct IdTree {
+    root: Link,
+}
+
+type Link = Option<Box<IdNode>>;
+
+#[derive(Clone, Debug)]
+struct IdNode {
+    id: u64,
+    /// Random priority, the treap is a heap on these
+    priority: u64,
+    /// Occurrences on each side, indexed by [`Side::index`]
+    counts: [u64; 2],
+    left: Link,
+    right: Link,
+    totals: Totals,
+}
+
+/// Aggregates over a subtree
+#[derive(Clone, Copy, Debug)]
+struct Totals {
+    counts: [u64; 2],
+    sums: [u128; 2],
+    /// Left count minus right count
+    net: i64,
+    /// Lowest and highest running `net` after each id, in id order
+    min_walk: i64,
+    max_walk: i64,
+}
+
+impl Totals {
+    const EMPTY: Totals = Totals {
+        counts: [0; 2],
+        sums: [0; 2],
+        net: 0,
+        min_walk: i64::MAX,
+        max_walk: i64::MIN,
+    };
+
+    fn of(link: &Link) -> Totals {
+        link.as_ref().map_or(Totals::EMPTY, |n| n.totals)
+    }
+
+    fn node(id: u64, counts: [u64; 2]) -> Totals {
+        let net = counts[0] as i64 - counts[1] as i64;
+        Totals {
+            counts,
+            sums: counts.map(|c| id as u128 * c as u128),
+            net,
+            min_walk: net,
+            max_walk: net,
+        }
+    }
+
+    /// `self` followed by `next`
+    fn then(self, next: Totals) -> Totals {
+        Totals {
+            counts: [0, 1].map(|s| self.counts[s] + next.counts[s]),
+            sums: [0, 1].map(|s| self.sums[s] + next.sums[s]),
+            net: self.net + next.net,
+            min_walk: self.min_walk.min(self.net.saturating_add(next.min_walk)),
+            max_walk: self.max_walk.max(self.net.saturating_add(next.max_walk)),
+        }
+    }
+}
+
+impl IdNode {
+    fn new(id: u64) -> Self {
+        IdNode {
+            id,
+            priority: Rng::new(id).next_u64(),
+            counts: [0; 2],
+            left: None,
+            right: None,
+            totals: Totals::EMPTY,
+        }
+    }
+
+    fn update(&mut self) {
+        self.totals = Totals::of(&self.left)
+            .then(Totals::node(self.id, self.counts))
+            .then(Totals::of(&self.right));
+    }
+}
+
+impl IdTree {
+    /// Change the count of `id` on `side` by `delta`. `structural` says the id is
+    /// new to the tree for an insert, or gone from both sides for a removal.
+    fn add(&mut self, side: Side, id: u64, delta: i64, structural: bool) {
+        if structural && delta > 0 {
+            let (below, rest) = split(self.root.take(), |x| x < id);
+            let mut node = Box::new(IdNode::new(id));
+            node.counts[side.index()] = 1;
+            node.update();
+            self.root = merge(merge(below, Some(node)), rest);
+        } else if structural {
+            let (below, rest) = split(self.root.take(), |x| x < id);
+            let (_, above) = split(rest, |x| x <= id);
+            self.root = merge(below, above);
+        } else {
+            add_count(&mut self.root, side, id, delta);
+        }
+    }
+
+    fn len(&self, side: Side) -> u64 {
+        Totals::of(&self.root).counts[side.index()]
+    }
+
+    /// The `rank`-th smallest id on `side`
+    fn select(&self, side: Side, mut rank: u64) -> Option<u64> {
+        let s = side.index();
+        let mut link = &self.root;
+        while let Some(node) = link {
+            let left = Totals::of(&node.left).counts[s];
+            if rank < left {
+                link = &node.left;
+            } else if rank < left + node.counts[s] {
+                return Some(node.id);
+            } else {
+                rank -= left + node.counts[s];
+                link = &node.right;
+            }
+        }
+        None
+    }
+
+    /// Totals over the ids for which `included` holds, which must be a prefix
+    fn prefix(&self, included: impl Fn(u64) -> bool) -> Totals {
+        let mut totals = Totals::EMPTY;
+        let mut link = &self.root;
+        while let Some(node) = link {
+            if included(node.id) {
+                totals = totals
+                    .then(Totals::of(&node.left))
+                    .then(Totals::node(node.id, node.counts));
+                link = &node.right;
+            } else {
+                link = &node.left;
+            }
+        }
+        totals
+    }
+
+    /// The integral of D(x) from the smallest id up to `x`
+    fn area_below(&self, x: u64) -> i128 {
+        let below = self.prefix(|id| id < x);
+        let area = |s: usize| x as i128 * below.counts[s] as i128 - below.sums[s] as i128;
+        area(0) - area(1)
+    }
+
+    /// Sorted distance of the `k` smallest ids of each side, `k` being the
+    /// shorter side's length.
+    ///
+    /// Say the left side is the shorter one and `t` is the k-th smallest right id.
+    /// Below `t` the area between the counting functions is that of the full
+    /// lists, from `t` on every right id is paired, so what is left is each
+    /// left id above `t` paired with something at most `t`.
+    fn dist(&self) -> u64 {
+        let all = Totals::of(&self.root);
+        let k = all.counts[0].min(all.counts[1]);
+        if k == 0 {
+            return 0;
+        }
+        let (short, long) = if all.counts[0] <= all.counts[1] {
+            (Side::Left, Side::Right)
+        } else {
+            (Side::Right, Side::Left)
+        };
+        let t = self.select(long, k - 1).unwrap();
+
+        let upto_t = self.prefix(|id| id <= t);
+        let s = short.index();
+        let mut dist =
+            (all.sums[s] - upto_t.sums[s]) - t as u128 * (all.counts[s] - upto_t.counts[s]) as u128;
+
+        // Between sign changes of D the area of |D| is the absolute area of D
+        let mut x = self
+            .select(Side::Left, 0)
+            .min(self.select(Side::Right, 0))
+            .unwrap();
+        let mut area = self.area_below(x);
+        while x < t {
+            let walk = self.prefix(|id| id <= x).net;
+            let sign = if walk >= 0 { 1 } else { -1 };
+            let end = first_crossing(&self.root, 0, x, sign).map_or(t, |c| c.min(t));
+            let end_area = self.area_below(end);
+            dist += (end_area - area).unsigned_abs();
+            (x, area) = (end, end_area);
+        }
+
+        u64::try_from(dist).expect("distance total overflows u64")
+    }
+}
+
+/// First id after `from` where the running D, starting at `walk`, has the
+/// opposite sign to `sign`
+fn first_crossing(link: &Link, walk: i64, from: u64, sign: i64) -> Option<u64> {
+    let node = link.as_ref()?;
+    let totals = node.totals;
+    let crosses = if sign > 0 {
+        walk.saturating_add(totals.min_walk) < 0
+    } else {
+        walk.saturating_add(totals.max_walk) > 0
+    };
+    if !crosses {
+        return None;
+    }
+
+    let after_node = walk + Totals::of(&node.left).net + Totals::node(node.id, node.counts).net;
+    if node.id <= from {
+        return first_crossing(&node.right, after_node, from, sign);
+    }
+    first_crossing(&node.left, walk, from, sign)
+        .or_else(|| (sign * after_node < 0).then_some(node.id))
+        .or_else(|| first_crossing(&node.right, after_node, from, sign))
+}
+
+/// Split into the ids for which `below` holds, which must be a prefix, and the rest
+fn split(link: Link, below: impl Fn(u64) -> bool + Copy) -> (Link, Link) {
+    let Some(mut node) = link else {
+        return (None, None);
+    };
+    if below(node.id) {
+        let (below, rest) = split(node.right.take(), below);
+        node.right = below;
+        node.update();
+        (Some(node), rest)
+    } else {
+        let (below, rest) = split(node.left.take(), below);
+        node.left = rest;
+        node.update();
+        (below, Some(node))
+    }
+}
+
+/// Join two treaps where every id in `a` is below every id in `b`
+fn merge(a: Link, b: Link) -> Link {
+    match (a, b) {
+        (None, link) | (link, None) => link,
+        (Some(mut a), Some(mut b)) => {
+            if a.priority > b.priority {
+                a.right = merge(a.right.take(), Some(b));
+                a.update();
+                Some(a)
+            } else {
+                b.left = merge(Some(a), b.left.take());
+                b.update();
+                Some(b)
+            }
+        }
+    }
+}
+
+fn add_count(link: &mut Link, side: Side, id: u64, delta: i64) {
+    let node = link.as_mut().expect("id is in the tree");
+    match id.cmp(&node.id) {
+        Ordering::Less => add_count(&mut node.left, side, id, delta),
+        Ordering::Greater => add_count(&mut node.right, side, id, delta),
+        Ordering::Equal => {
+            let count = &mut node.counts[side.index()];
+            *count = count.checked_add_signed(delta).unwrap();
+        }
+    }
+    node.update();
+}
+
+impl Side {
+    pub fn other(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
@@ -1,72 +1,107 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::File,
-    io::{self, BufRead},
-    path::Path,
+use common::{
+    report::{self, DayRecord, PartResult, timed},
+    submit::{GuessStore, Submission},
+};
+use day1::{
+    ExternalLists, MetricRegistry, PUZZLE, dist_sum, largest_contributors, lines_to_columns,
+    lines_to_locs, matrix_table, pairing_table, pairings, read_lines, score_matrix, similarity_sum,
 };
 
 fn main() {
-    let lines = read_lines("input");
-    let sum = dist_sum(lines);
-    println!("dist sum: {}", sum);
-
-    let lines = read_lines("input");
-    let sum = similarity_sum(lines);
-    println!("similarity sum: {}", sum);
-}
+    if std::env::args().nth(1).as_deref() == Some("pairs") {
+        let top = std::env::args()
+            .nth(2)
+            .map(|n| n.parse().expect("invalid contributor count"))
+            .unwrap_or(10);
+        let filename = std::env::args().nth(3).unwrap_or("input".to_string());
+        let locs = lines_to_locs(read_lines(filename)).unwrap();
 
-fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename).unwrap();
-    io::BufReader::new(file)
-        .lines()
-        .map(|l| l.unwrap())
-        .filter(|l| !l.trim().is_empty())
-}
+        let pairs = pairings(&locs);
+        let total = dist_sum(&locs);
+        println!("Largest {} contributors to {}:", top, total);
+        print!(
+            "{}",
+            pairing_table(&largest_contributors(&pairs, top), total)
+        );
+        println!();
+        println!("All {} pairs:", pairs.len());
+        print!("{}", pairing_table(&pairs, total));
+        return;
+    }
 
-fn line_to_locs(line: &str) -> (u64, u64) {
-    let mut elems = line.split_ascii_whitespace();
-    let left = elems.next().unwrap();
-    let right = elems.next().unwrap();
+    if std::env::args().nth(1).as_deref() == Some("similarity") {
+        let registry = MetricRegistry::new();
+        let metric = std::env::args().nth(2).filter(|m| m != "all");
+        let filename = std::env::args().nth(3).unwrap_or("input".to_string());
+        let locs = lines_to_locs(read_lines(filename)).unwrap();
+        let left = locs.iter().map(|(l, _)| *l).collect::<Vec<_>>();
+        let right = locs.iter().map(|(_, r)| *r).collect::<Vec<_>>();
 
-    let left: u64 = left.parse().unwrap();
-    let right: u64 = right.parse().unwrap();
+        let metrics = match &metric {
+            Some(name) => match registry.get(name) {
+                Some(metric) => vec![metric],
+                None => {
+                    let names = registry.metrics().map(|m| m.name()).collect::<Vec<_>>();
+                    eprintln!(
+                        "unknown metric {}, expected one of: {}",
+                        name,
+                        names.join(", ")
+                    );
+                    std::process::exit(1);
+                }
+            },
+            None => registry.metrics().collect(),
+        };
+        for metric in metrics {
+            println!("{}: {}", metric.name(), metric.score(&left, &right));
+        }
+        return;
+    }
 
-    (left, right)
-}
+    if std::env::args().nth(1).as_deref() == Some("matrix") {
+        let filename = std::env::args().nth(2).unwrap_or("input".to_string());
+        let columns = lines_to_columns(read_lines(filename)).unwrap();
 
-fn dist_sum(lines: impl Iterator<Item = String>) -> u64 {
-    let locs = lines.map(|l| line_to_locs(&l)).collect::<Vec<_>>();
+        let matrix = score_matrix(&columns);
+        println!("Sorted distance:");
+        print!("{}", matrix_table(&matrix.dist));
+        println!();
+        println!("Similarity:");
+        print!("{}", matrix_table(&matrix.similarity));
+        return;
+    }
 
-    let mut left_locs = locs.iter().map(|(l, _)| *l).collect::<Vec<_>>();
-    let mut right_locs = locs.iter().map(|(_, r)| *r).collect::<Vec<_>>();
+    if std::env::args().nth(1).as_deref() == Some("external") {
+        let chunk_len = std::env::args()
+            .nth(2)
+            .map(|n| n.parse().expect("invalid chunk length"))
+            .unwrap_or(1 << 20);
+        let filename = std::env::args().nth(3).unwrap_or("input".to_string());
+        let lists = ExternalLists::from_lines(read_lines(filename), chunk_len).unwrap();
 
-    left_locs.sort();
-    right_locs.sort();
+        println!("dist sum: {}", lists.dist_sum().unwrap());
+        println!("similarity sum: {}", lists.similarity_sum().unwrap());
+        return;
+    }
 
-    left_locs
-        .into_iter()
-        .zip(right_locs.into_iter())
-        .map(|(l, r)| l.abs_diff(r))
-        .sum::<u64>()
-}
+    let locs = lines_to_locs(read_lines("input")).unwrap();
+    let submit = Submission::from_args(PUZZLE);
 
-fn similarity_sum(lines: impl Iterator<Item = String>) -> u64 {
-    let locs = lines.map(|l| line_to_locs(&l)).collect::<Vec<_>>();
+    let (dist, time1) = timed(|| dist_sum(&locs));
+    println!("dist sum: {}", dist);
+    submit.answer(1, dist);
 
-    let left_locs = locs.iter().map(|(l, _)| *l).collect::<Vec<_>>();
-    let right_locs_freq = locs
-        .iter()
-        .map(|(_, r)| *r)
-        .fold(HashMap::new(), |mut acc, r| {
-            acc.insert(r, acc.get(&r).unwrap_or(&0) + 1 as u64);
-            acc
-        });
+    let (similarity, time2) = timed(|| similarity_sum(&locs));
+    println!("similarity sum: {}", similarity);
+    submit.answer(2, similarity);
 
-    left_locs
-        .into_iter()
-        .map(|l| l * right_locs_freq.get(&l).unwrap_or(&0))
-        .sum::<u64>()
+    if let Some(path) = report::path_from_args(std::env::args()) {
+        let example = lines_to_locs(read_lines("test")).unwrap();
+        let record = DayRecord::new(PUZZLE, "Historian Hysteria")
+            .part(PartResult::new(dist, time1))
+            .part(PartResult::new(similarity, time2))
+            .example(dist_sum(&example) == 11 && similarity_sum(&example) == 31)
+            .verify(&GuessStore::from_env().unwrap());
+        report::merge(path, record).unwrap();
+    }
 }
@@ -0,0 +1,40 @@
+use common::rng::Rng;
+use day1::{ExternalLists, dist_sum, lines_to_locs, read_lines, similarity_sum};
+
+fn check(lines: &[String]) {
+    let locs = lines_to_locs(lines.iter().cloned()).unwrap();
+    for chunk_len in [1, 3] {
+        let lists = ExternalLists::from_lines(lines.iter().cloned(), chunk_len).unwrap();
+        assert_eq!(
+            lists.dist_sum().unwrap(),
+            dist_sum(&locs),
+            "chunk {}",
+            chunk_len
+        );
+        assert_eq!(
+            lists.similarity_sum().unwrap(),
+            similarity_sum(&locs),
+            "chunk {}",
+            chunk_len
+        );
+    }
+}
+
+#[test]
+fn matches_in_memory_on_inputs() {
+    check(&read_lines("test").collect::<Vec<_>>());
+    check(&read_lines("input").collect::<Vec<_>>());
+}
+
+#[test]
+fn matches_in_memory_on_random_lists() {
+    let mut rng = Rng::new(42);
+    for _ in 0..20 {
+        // Few distinct ids so the right counts repeat across spilled runs
+        let len = rng.range(0..60) as usize;
+        let lines = (0..len)
+            .map(|_| format!("{}   {}", rng.below(10), rng.below(10)))
+            .collect::<Vec<_>>();
+        check(&lines);
+    }
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day1::parse, seed = 1, inputs = ["input"],);
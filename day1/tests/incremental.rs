@@ -0,0 +1,103 @@
+use common::rng::Rng;
+use day1::{
+    IncrementalLists, Side, column_dist, column_similarity, dist_sum, lines_to_locs, read_lines,
+    similarity_sum,
+};
+
+/// Random inserts and removes, checking every total against a full recompute
+fn check_random(seed: u64, steps: usize, max_id: u64) {
+    let mut rng = Rng::new(seed);
+    let mut lists = IncrementalLists::new();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for step in 0..steps {
+        let side = if rng.chance(0.5) {
+            Side::Left
+        } else {
+            Side::Right
+        };
+        let model = match side {
+            Side::Left => &mut left,
+            Side::Right => &mut right,
+        };
+        let id = rng.below(max_id);
+
+        // Lean towards inserts so the lists grow, and remove ids that are there
+        // more often than ones that are not
+        if rng.chance(0.6) {
+            lists.insert(side, id);
+            model.push(id);
+        } else {
+            let id = if !model.is_empty() && rng.chance(0.8) {
+                model[rng.below(model.len() as u64) as usize]
+            } else {
+                id
+            };
+            let pos = model.iter().position(|&x| x == id);
+            assert_eq!(lists.remove(side, id), pos.is_some(), "step {}", step);
+            if let Some(pos) = pos {
+                model.swap_remove(pos);
+            }
+        }
+
+        assert_eq!(lists.len(Side::Left), left.len());
+        assert_eq!(lists.len(Side::Right), right.len());
+        assert_eq!(lists.is_empty(), left.is_empty() && right.is_empty());
+        assert_eq!(
+            lists.dist_sum(),
+            column_dist(&left, &right),
+            "step {}",
+            step
+        );
+        assert_eq!(
+            lists.similarity_sum(),
+            column_similarity(&left, &right),
+            "step {}",
+            step
+        );
+
+        let mut sorted = left.clone();
+        sorted.sort();
+        for (rank, id) in sorted.iter().enumerate() {
+            assert_eq!(lists.select(Side::Left, rank), Some(*id));
+        }
+        assert_eq!(lists.select(Side::Left, sorted.len()), None);
+    }
+}
+
+#[test]
+fn matches_full_recompute_with_repeated_ids() {
+    for seed in 0..20 {
+        check_random(seed, 300, 12);
+    }
+}
+
+#[test]
+fn matches_full_recompute_with_spread_ids() {
+    for seed in 0..5 {
+        check_random(seed, 500, 1 << 40);
+    }
+}
+
+#[test]
+fn matches_parts_on_inputs() {
+    for file in ["test", "input"] {
+        let locs = lines_to_locs(read_lines(file)).unwrap();
+        let lists = IncrementalLists::from_locs(&locs);
+        assert_eq!(lists.dist_sum(), dist_sum(&locs));
+        assert_eq!(lists.similarity_sum(), similarity_sum(&locs));
+    }
+}
+
+#[test]
+fn extreme_ids() {
+    let mut lists = IncrementalLists::new();
+    lists.insert(Side::Left, u64::MAX);
+    lists.insert(Side::Right, 0);
+    assert_eq!(lists.dist_sum(), u64::MAX);
+    assert!(lists.remove(Side::Left, u64::MAX));
+    assert!(!lists.remove(Side::Left, u64::MAX));
+    assert_eq!(lists.dist_sum(), 0);
+    assert_eq!(lists.select(Side::Right, 0), Some(0));
+}
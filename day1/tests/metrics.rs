@@ -0,0 +1,91 @@
+use day1::{MetricRegistry, Score, SimilarityMetric, lines_to_locs, read_lines};
+
+fn example() -> (Vec<u64>, Vec<u64>) {
+    lines_to_locs(read_lines("test"))
+        .unwrap()
+        .into_iter()
+        .unzip()
+}
+
+/// Ids that appear on the left but not the right
+struct LeftOnly;
+
+impl SimilarityMetric for LeftOnly {
+    fn name(&self) -> &str {
+        "left-only"
+    }
+
+    fn score(&self, left: &[u64], right: &[u64]) -> Score {
+        Score::Count(left.iter().filter(|id| !right.contains(id)).count() as u64)
+    }
+}
+
+#[test]
+fn built_in_scores_on_example() {
+    let (left, right) = example();
+    let registry = MetricRegistry::new();
+    let scores = registry
+        .metrics()
+        .map(|m| (m.name().to_string(), m.score(&left, &right)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        scores,
+        [
+            ("count-weighted".to_string(), Score::Count(31)),
+            ("intersection".to_string(), Score::Count(4)),
+            ("jaccard".to_string(), Score::Ratio(2.0 / 6.0)),
+            ("weighted-overlap".to_string(), Score::Count(13)),
+        ]
+    );
+}
+
+#[test]
+fn registers_custom_metric() {
+    let (left, right) = example();
+    let mut registry = MetricRegistry::new();
+    assert!(registry.get("left-only").is_none());
+
+    registry.register(LeftOnly);
+    let metric = registry.get("left-only").unwrap();
+    assert_eq!(metric.score(&left, &right), Score::Count(2));
+    assert_eq!(registry.metrics().count(), 5);
+    assert_eq!(
+        registry.get("count-weighted").unwrap().score(&left, &right),
+        Score::Count(31)
+    );
+}
+
+#[test]
+fn registering_replaces_same_name() {
+    struct Constant;
+
+    impl SimilarityMetric for Constant {
+        fn name(&self) -> &str {
+            "jaccard"
+        }
+
+        fn score(&self, _: &[u64], _: &[u64]) -> Score {
+            Score::Ratio(1.0)
+        }
+    }
+
+    let (left, right) = example();
+    let mut registry = MetricRegistry::new();
+    registry.register(Constant);
+    assert_eq!(registry.metrics().count(), 4);
+    assert_eq!(
+        registry.get("jaccard").unwrap().score(&left, &right),
+        Score::Ratio(1.0)
+    );
+}
+
+#[test]
+fn counts_beyond_f64_precision_are_exact() {
+    // 2^53 + 1 is the first integer an f64 cannot hold
+    let id = (1 << 53) + 1;
+    let registry = MetricRegistry::new();
+    let score = registry.get("count-weighted").unwrap().score(&[id], &[id]);
+    assert_eq!(score, Score::Count(id));
+    assert_eq!(score.to_string(), "9007199254740993");
+}
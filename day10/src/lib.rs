@@ -0,0 +1,132 @@
+//! Day 10: Hoof It
+
+use std::{collections::HashSet, io::BufRead, path::Path};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 10);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// Grid of heights 0 to 9
+pub fn lines_to_grid(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<u8>>, ParseError> {
+    let grid = parse::grid(lines, |c| c.is_ascii_digit())?;
+
+    Ok(grid
+        .into_iter()
+        .map(|row| row.into_iter().map(|c| c as u8 - b'0').collect())
+        .collect())
+}
+
+/// [`lines_to_grid`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    lines_to_grid(parse::text_lines(text))
+}
+
+/// In-bounds orthogonal neighbours of (x, y)
+pub fn adj_grid_pos<R>(x: usize, y: usize, grid: &[R]) -> impl Iterator<Item = (usize, usize)>
+where
+    R: AsRef<[u8]>,
+{
+    let x = x as isize;
+    let y = y as isize;
+    let x_max = grid[0].as_ref().len() as isize;
+    let y_max = grid.len() as isize;
+
+    [(0, 1), (1, 0), (0, -1), (-1, 0)]
+        .iter()
+        .map(move |(dx, dy)| (x + dx, y + dy))
+        .filter(move |(x, y)| *x >= 0 && *y >= 0 && *x < x_max && *y < y_max)
+        .map(|(x, y)| (x as usize, y as usize))
+}
+
+/// Distinct 9s reachable from (x, y) by uphill steps of one
+pub fn nines_from_zero<R>(x: usize, y: usize, grid: &[R]) -> usize
+where
+    R: AsRef<[u8]>,
+{
+    let mut visited = HashSet::new();
+    let mut nines = HashSet::new();
+    let mut to_visit = vec![((x, y), 0)];
+
+    while let Some(((x, y), e)) = to_visit.pop() {
+        if grid[y].as_ref()[x] != e {
+            continue;
+        }
+
+        if visited.contains(&(x, y)) {
+            continue;
+        }
+        visited.insert((x, y));
+
+        if grid[y].as_ref()[x] == 9 {
+            nines.insert((x, y));
+            continue;
+        }
+
+        let ne = e + 1;
+        for (nx, ny) in adj_grid_pos(x, y, grid) {
+            to_visit.push(((nx, ny), ne));
+        }
+    }
+
+    nines.len()
+}
+
+/// Distinct uphill trails from (x, y) to any 9
+pub fn path_count_from_zero<R>(x: usize, y: usize, grid: &[R]) -> usize
+where
+    R: AsRef<[u8]>,
+{
+    let mut nines = 0;
+    let mut to_visit = vec![((x, y), 0)];
+
+    while let Some(((x, y), e)) = to_visit.pop() {
+        if grid[y].as_ref()[x] != e {
+            continue;
+        }
+
+        if grid[y].as_ref()[x] == 9 {
+            nines += 1;
+            continue;
+        }
+
+        let ne = e + 1;
+        for (nx, ny) in adj_grid_pos(x, y, grid) {
+            to_visit.push(((nx, ny), ne));
+        }
+    }
+
+    nines
+}
+
+/// Part 1: sum of trailhead scores
+pub fn all_trails_count(grid: &[Vec<u8>]) -> usize {
+    (0..grid.len())
+        .flat_map(|y| (0..grid[y].len()).map(move |x| (x, y)))
+        .map(|(x, y)| nines_from_zero(x, y, grid))
+        .sum()
+}
+
+/// Part 2: sum of trailhead ratings
+pub fn trailhead_rating_sum(grid: &[Vec<u8>]) -> usize {
+    (0..grid.len())
+        .flat_map(|y| (0..grid[y].len()).map(move |x| (x, y)))
+        .map(|(x, y)| path_count_from_zero(x, y, grid))
+        .sum()
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day10::parse, seed = 10, inputs = ["input"],);
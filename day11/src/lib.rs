@@ -0,0 +1,115 @@
+//! Day 11: Plutonian Pebbles
+
+use std::{collections::HashMap, io::BufRead, path::Path};
+
+use common::{
+    compare::Comparison,
+    input,
+    parse::{self, ParseError},
+    progress::Progress,
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 11);
+
+common::embed_inputs!("input", "test");
+
+/// First non-empty line of `filename`, empty if there is none
+pub fn read_line<P>(filename: P) -> String
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default()
+}
+
+pub fn line_to_stones(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split_ascii_whitespace().map(parse::number).collect()
+}
+
+/// [`line_to_stones`] on the first non-empty line of an input file
+pub fn parse(text: &str) -> Result<Vec<u64>, ParseError> {
+    line_to_stones(parse::text_lines(text).next().as_deref().unwrap_or(""))
+}
+
+/// Stones that `stone` turns into after one blink
+pub fn child_stones(stone: u64) -> impl Iterator<Item = u64> {
+    let mut output_arr = [None, None];
+    if stone == 0 {
+        output_arr[0] = Some(1);
+    } else {
+        let log = f64::log10(stone as f64) as u32;
+        if log % 2 == 1 {
+            let splitter = 10u64.pow(log.div_ceil(2));
+            let left = stone / splitter;
+            let right = stone % splitter;
+
+            output_arr[0] = Some(left);
+            output_arr[1] = Some(right);
+        } else {
+            output_arr[0] = Some(stone * 2024);
+        }
+    }
+
+    output_arr.into_iter().flatten()
+}
+
+/// Every stone after `n` blinks, one by one
+pub fn levels<'a, I>(stones: I, n: usize) -> impl Iterator<Item = u64> + 'a
+where
+    I: Iterator<Item = u64> + 'a,
+{
+    let mut stones: Box<dyn Iterator<Item = u64> + 'a> = Box::new(stones);
+    for _ in 0..n {
+        stones = Box::new(stones.flat_map(child_stones));
+    }
+
+    stones
+}
+
+/// Part 1: stone count after `n` blinks, by enumerating every stone
+pub fn stone_count(stones: &[u64], n: usize) -> usize {
+    levels(stones.iter().copied(), n).count()
+}
+
+/// Counts of each distinct stone after `n` blinks
+pub fn levels_w_collapse<I>(stones: I, n: usize) -> impl Iterator<Item = u64>
+where
+    I: Iterator<Item = u64>,
+{
+    let mut stones_w_count = stones.map(|s| (s, 1)).collect::<HashMap<_, _>>();
+    let mut progress = Progress::counted("blinks", n as u64);
+    for _ in 0..n {
+        let mut new_stones = HashMap::new();
+        for (s, c) in stones_w_count
+            .iter()
+            .flat_map(|(s, c)| child_stones(*s).map(|cs| (cs, *c)))
+        {
+            let count = new_stones.entry(s).or_insert(0);
+            *count += c;
+        }
+        stones_w_count = new_stones;
+        progress.tick();
+    }
+
+    stones_w_count.into_values()
+}
+
+/// Part 2: stone count after `n` blinks, counting equal stones together
+pub fn stone_count_w_collapse(stones: &[u64], n: usize) -> u64 {
+    levels_w_collapse(stones.iter().copied(), n).sum()
+}
+
+/// Both stone counting implementations, for checking one against the other
+pub fn stone_count_comparison(n: usize) -> Comparison<Vec<u64>, u64> {
+    Comparison::new()
+        .implementation("levels", move |stones: &Vec<u64>| {
+            stone_count(stones, n) as u64
+        })
+        .implementation("collapse", move |stones: &Vec<u64>| {
+            stone_count_w_collapse(stones, n)
+        })
+}
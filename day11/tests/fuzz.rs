@@ -0,0 +1 @@
+common::parser_fuzz_test!(day11::parse, seed = 11, inputs = ["input", "test"],);
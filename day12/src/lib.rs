@@ -0,0 +1,169 @@
+//! Day 12: Garden Groups
+
+use std::{io::BufRead, path::Path};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+    union_find,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 12);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// Garden plots labelled with their plant, A to Z
+pub fn lines_to_grid(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<char>>, ParseError> {
+    parse::grid(lines, |c| c.is_ascii_uppercase())
+}
+
+/// [`lines_to_grid`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    lines_to_grid(parse::text_lines(text))
+}
+
+pub struct Region {
+    pub perimeter: usize,
+    pub area: usize,
+}
+
+/// Fence segments around (x, y) that border another region or the map edge
+pub fn cell_perimeter<R>(grid: &[R], x: usize, y: usize) -> usize
+where
+    R: AsRef<[char]>,
+{
+    let region_label = grid[y].as_ref()[x];
+    let mut perimeter = 0;
+
+    if x == 0 || x == grid[0].as_ref().len() - 1 {
+        perimeter += 1;
+    }
+    if y == 0 || y == grid.len() - 1 {
+        perimeter += 1;
+    }
+    for (x, y) in adj_grid_pos(x, y, grid) {
+        if grid[y].as_ref()[x] != region_label {
+            perimeter += 1;
+        }
+    }
+
+    perimeter
+}
+
+/// Like [`cell_perimeter`], but only counting the first cell along each straight side
+pub fn cell_perimeter_bulk<R>(grid: &[R], x: usize, y: usize) -> usize
+where
+    R: AsRef<[char]>,
+{
+    let region_label = grid[y].as_ref()[x];
+    let mut perimeter = 0;
+
+    // Not downward continuation edge
+    if (x == 0 || x == grid[0].as_ref().len() - 1)
+        && (y == 0 || grid[y - 1].as_ref()[x] != region_label)
+    {
+        perimeter += 1;
+    }
+    // Not rightward continuation edge
+    if (y == 0 || y == grid.len() - 1) && (x == 0 || grid[y].as_ref()[x - 1] != region_label) {
+        perimeter += 1;
+    }
+    for (x2, y2) in adj_grid_pos(x, y, grid) {
+        if grid[y2].as_ref()[x2] == region_label {
+            continue;
+        }
+
+        // Start of row?
+        let dx = x2 as isize - x as isize;
+        if dx != 0 {
+            // Vertical edge
+            if y == 0
+                || grid[y - 1].as_ref()[x] != region_label
+                || grid[y - 1].as_ref()[x2] == region_label
+            {
+                // Not downward continuation edge
+                perimeter += 1;
+            }
+        } else {
+            // dy != 0
+            // Horizontal edge
+            if x == 0
+                || grid[y].as_ref()[x - 1] != region_label
+                || grid[y2].as_ref()[x - 1] == region_label
+            {
+                // Not rightward continuation edge
+                perimeter += 1;
+            }
+        }
+    }
+
+    perimeter
+}
+
+/// Regions of equal plants, with their perimeter summed from `cell_perimeter`
+pub fn grid_regions<R>(
+    grid: &[R],
+    cell_perimeter: impl Fn(&[R], usize, usize) -> usize,
+) -> Vec<Region>
+where
+    R: AsRef<[char]>,
+{
+    let components = union_find::label_grid(grid, |a, b| a == b);
+
+    let mut regions = components
+        .sizes
+        .iter()
+        .map(|&area| Region { perimeter: 0, area })
+        .collect::<Vec<_>>();
+    for (y, row) in components.labels.iter().enumerate() {
+        for (x, &region_id) in row.iter().enumerate() {
+            regions[region_id].perimeter += cell_perimeter(grid, x, y);
+        }
+    }
+
+    regions
+}
+
+fn adj_grid_pos<R>(x: usize, y: usize, grid: &[R]) -> impl Iterator<Item = (usize, usize)>
+where
+    R: AsRef<[char]>,
+{
+    let x = x as isize;
+    let y = y as isize;
+    let x_max = grid[0].as_ref().len() as isize;
+    let y_max = grid.len() as isize;
+
+    [(0, 1), (1, 0), (0, -1), (-1, 0)]
+        .iter()
+        .map(move |(dx, dy)| (x + dx, y + dy))
+        .filter(move |(x, y)| *x >= 0 && *y >= 0 && *x < x_max && *y < y_max)
+        .map(|(x, y)| (x as usize, y as usize))
+}
+
+/// Part 1: sum of area * perimeter
+pub fn region_price_sum(grid: &[Vec<char>]) -> usize {
+    grid_regions(grid, cell_perimeter)
+        .iter()
+        .map(|r| r.perimeter * r.area)
+        .sum()
+}
+
+/// Part 2: sum of area * number of sides
+pub fn region_price_sum_bulk(grid: &[Vec<char>]) -> usize {
+    grid_regions(grid, cell_perimeter_bulk)
+        .iter()
+        .map(|r| r.perimeter * r.area)
+        .sum()
+}
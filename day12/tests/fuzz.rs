@@ -0,0 +1 @@
+common::parser_fuzz_test!(day12::parse, seed = 12, inputs = ["input", "test"],);
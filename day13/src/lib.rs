@@ -0,0 +1,154 @@
+//! Day 13: Claw Contraption
+
+use std::{io::BufRead, path::Path};
+
+use common::{
+    input, num,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 13);
+
+common::embed_inputs!("input", "test", "test2");
+
+/// Blank line separated groups of lines in `filename`
+pub fn read_inputs<P>(filename: P) -> impl Iterator<Item = Vec<String>>
+where
+    P: AsRef<Path>,
+{
+    lines_to_inputs(
+        input::open(PUZZLE, filename, EMBEDDED)
+            .lines()
+            .map(|l| l.unwrap()),
+    )
+}
+
+/// Split `lines` into blank line separated groups
+pub fn lines_to_inputs(lines: impl Iterator<Item = String>) -> impl Iterator<Item = Vec<String>> {
+    let lines: Vec<_> = lines.collect();
+
+    lines
+        .split(|l| l.is_empty())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// One claw machine: the (x, y) movement of each button and the prize location
+#[derive(Clone, Debug)]
+pub struct Spec {
+    pub a: (usize, usize),
+    pub b: (usize, usize),
+    pub target: (usize, usize),
+}
+
+/// Fewest tokens (3 per A press, 1 per B press) to reach the prize, if it can be
+/// reached. Machines whose numbers do not fit in an `i64`, or whose token cost
+/// does not fit in a `usize`, count as unwinnable.
+pub fn solve_spec(spec: &Spec) -> Option<usize> {
+    let int = |n: usize| i64::try_from(n).ok();
+    let (a, b) = num::solve_2x2(
+        [
+            [int(spec.a.0)?, int(spec.b.0)?],
+            [int(spec.a.1)?, int(spec.b.1)?],
+        ],
+        [int(spec.target.0)?, int(spec.target.1)?],
+    )?;
+
+    // Buttons can't be pressed a negative number of times
+    let a = usize::try_from(a).ok()?;
+    let b = usize::try_from(b).ok()?;
+
+    a.checked_mul(3)?.checked_add(b)
+}
+
+/// Parse the A button, B button and prize lines of one machine
+pub fn input_to_spec(input: impl Iterator<Item = String>) -> Result<Spec, ParseError> {
+    let lines = input.collect::<Vec<_>>();
+    let [a_button, b_button, result] = &lines[..] else {
+        return Err(ParseError::new(format!(
+            "expected 3 lines per machine, found {}",
+            lines.len()
+        )));
+    };
+
+    Ok(Spec {
+        a: button_to_bspec(a_button).map_err(|e| e.at_line(1))?,
+        b: button_to_bspec(b_button).map_err(|e| e.at_line(2))?,
+        target: result_to_rspec(result).map_err(|e| e.at_line(3))?,
+    })
+}
+
+/// `Button A: X+94, Y+34`
+fn button_to_bspec(button: &str) -> Result<(usize, usize), ParseError> {
+    line_to_coords(button, '+')
+}
+
+/// `Prize: X=8400, Y=5400`
+fn result_to_rspec(result: &str) -> Result<(usize, usize), ParseError> {
+    line_to_coords(result, '=')
+}
+
+/// The two numbers in `<label>: X<sep><x>, Y<sep><y>`
+fn line_to_coords(line: &str, sep: char) -> Result<(usize, usize), ParseError> {
+    let (_, coords) = line
+        .split_once(": ")
+        .ok_or_else(|| ParseError::new("expected <label>: <coords>"))?;
+    let (x, y) = coords
+        .split_once(", ")
+        .ok_or_else(|| ParseError::new("expected X and Y separated by \", \""))?;
+
+    let coord = |c: &str| {
+        let (_, n) = c
+            .split_once(sep)
+            .ok_or_else(|| ParseError::new(format!("expected {:?} in {:?}", sep, c)))?;
+        parse::number(n)
+    };
+
+    Ok((coord(x)?, coord(y)?))
+}
+
+/// Parse every machine, tagging errors with the machine they are in
+pub fn inputs_to_specs<T>(inputs: impl Iterator<Item = T>) -> Result<Vec<Spec>, ParseError>
+where
+    T: IntoIterator<Item = String>,
+{
+    inputs
+        .enumerate()
+        .map(|(i, input)| {
+            input_to_spec(input.into_iter()).map_err(|e| ParseError {
+                msg: format!("machine {}: {}", i + 1, e.msg),
+                ..e
+            })
+        })
+        .collect()
+}
+
+/// [`inputs_to_specs`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Spec>, ParseError> {
+    inputs_to_specs(lines_to_inputs(text.lines().map(|l| l.to_string())))
+}
+
+/// Part 1: tokens to win every winnable prize
+pub fn token_count(specs: &[Spec]) -> usize {
+    specs.iter().filter_map(solve_spec).sum()
+}
+
+/// How much further along both axes the part 2 prizes are
+pub const PRIZE_SHIFT: usize = 10000000000000;
+
+/// Part 2: [`token_count`] with prizes moved [`PRIZE_SHIFT`] further along both
+/// axes. Prizes that would move past `usize::MAX` are unwinnable.
+pub fn token_count_shifted(specs: &[Spec]) -> usize {
+    specs
+        .iter()
+        .filter_map(|spec| {
+            let mut spec = spec.clone();
+            spec.target.0 = spec.target.0.checked_add(PRIZE_SHIFT)?;
+            spec.target.1 = spec.target.1.checked_add(PRIZE_SHIFT)?;
+            solve_spec(&spec)
+        })
+        .sum()
+}
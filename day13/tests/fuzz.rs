@@ -0,0 +1,12 @@
+common::parser_fuzz_test!(
+    day13::parse,
+    seed = 13,
+    inputs = ["input", "test", "test2"],
+    // Inputs that once made the parser panic
+    regressions = [
+        b"But",
+        b"Button A: X+5, Y+20\nButton B: X+3, Y+12\nPrize: X=30, Y=120\n\nButton A: X+99999999999999999999999, Y+52\nButton B: X+67, Y+1",
+        b"Button A: X+5,",
+        b"Button pA: X\xe7++20\nButton B:",
+    ],
+);
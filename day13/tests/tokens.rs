@@ -0,0 +1,47 @@
+use day13::{
+    Spec, inputs_to_specs, parse, read_inputs, solve_spec, token_count, token_count_shifted,
+};
+
+#[test]
+fn example() {
+    let specs = inputs_to_specs(read_inputs("test")).unwrap();
+    assert_eq!(token_count(&specs), 480);
+}
+
+#[test]
+fn numbers_beyond_i64_are_unwinnable() {
+    let specs = parse(
+        "Button A: X+18446744073709551615, Y+1\n\
+         Button B: X+1, Y+1\n\
+         Prize: X=1, Y=3\n",
+    )
+    .unwrap();
+    assert_eq!(token_count(&specs), 0);
+}
+
+#[test]
+fn token_cost_beyond_usize_is_unwinnable() {
+    let presses = i64::MAX as usize;
+    let spec = Spec {
+        a: (1, 1),
+        b: (1, 2),
+        target: (presses, presses),
+    };
+    assert_eq!(solve_spec(&spec), None);
+
+    let spec = Spec {
+        target: (1000, 1000),
+        ..spec
+    };
+    assert_eq!(solve_spec(&spec), Some(3000));
+}
+
+#[test]
+fn shift_past_usize_is_unwinnable() {
+    let spec = Spec {
+        a: (1, 0),
+        b: (0, 1),
+        target: (usize::MAX - 1, 1),
+    };
+    assert_eq!(token_count_shifted(&[spec]), 0);
+}
@@ -0,0 +1,170 @@
+//! Day 14: Restroom Redoubt
+
+use std::{
+    io::{self, BufRead},
+    path::Path,
+};
+
+use common::{
+    input, num,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+    repl::{Repl, Simulation},
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 14);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+#[derive(Clone)]
+pub struct Robot {
+    pub x: i64,
+    pub y: i64,
+    pub vx: i64,
+    pub vy: i64,
+}
+
+/// The `x` and `y` of `<name>=x,y`
+fn split_pair(s: &str) -> Result<(&str, &str), ParseError> {
+    s.split_once("=")
+        .and_then(|(_, xy)| xy.split_once(","))
+        .map(|(x, y)| (x.trim(), y.trim()))
+        .ok_or_else(|| ParseError::new(format!("expected <name>=<x>,<y>: {:?}", s)))
+}
+
+/// Parse `p=x,y v=vx,vy`, positions can't be negative
+pub fn line_to_robot(line: &str) -> Result<Robot, ParseError> {
+    let (pos, vel) = line
+        .split_once(" ")
+        .ok_or_else(|| ParseError::new("expected p=x,y v=vx,vy"))?;
+    let (x, y) = split_pair(pos)?;
+    let (vx, vy) = split_pair(vel)?;
+
+    Ok(Robot {
+        x: parse::number::<u32>(x)?.into(),
+        y: parse::number::<u32>(y)?.into(),
+        vx: parse::number(vx)?,
+        vy: parse::number(vy)?,
+    })
+}
+
+/// Position after `t` seconds, wrapping around the `x_limit` by `y_limit` room
+pub fn future_pos(robot: &Robot, t: usize, x_limit: usize, y_limit: usize) -> (usize, usize) {
+    let (x_limit, y_limit) = (x_limit as i64, y_limit as i64);
+    let dx = num::mul_mod(robot.vx, t as i64, x_limit);
+    let dy = num::mul_mod(robot.vy, t as i64, y_limit);
+
+    let x = num::add_mod(robot.x, dx, x_limit);
+    let y = num::add_mod(robot.y, dy, y_limit);
+
+    (x as usize, y as usize)
+}
+
+fn pos_to_quadrants(x: usize, y: usize, x_limit: usize, y_limit: usize) -> Option<u8> {
+    let x_mid = x_limit / 2;
+    let x_quadrant = if x < x_mid {
+        0b00
+    } else if x > x_mid {
+        0b01
+    } else {
+        return None;
+    };
+    let y_mid = y_limit / 2;
+    let y_quadrant = if y < y_mid {
+        0b00
+    } else if y > y_mid {
+        0b10
+    } else {
+        return None;
+    };
+
+    Some(x_quadrant | y_quadrant)
+}
+
+pub fn lines_to_robots(lines: impl Iterator<Item = String>) -> Result<Vec<Robot>, ParseError> {
+    parse::lines_with(lines, line_to_robot)
+}
+
+/// [`lines_to_robots`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Robot>, ParseError> {
+    lines_to_robots(parse::text_lines(text))
+}
+
+/// Part 1: product of the robot counts in each quadrant after `t` seconds
+pub fn safety_factor(robots: &[Robot], t: usize, x_limit: usize, y_limit: usize) -> usize {
+    robots
+        .iter()
+        .map(|r| future_pos(r, t, x_limit, y_limit))
+        .filter_map(|(x, y)| pos_to_quadrants(x, y, x_limit, y_limit))
+        .fold([0; 4], |mut acc, q| {
+            acc[q as usize] += 1;
+            acc
+        })
+        .into_iter()
+        .reduce(|a, b| a * b)
+        .unwrap()
+}
+
+pub fn step_robots(robots: &mut [Robot], t: usize, x_limit: usize, y_limit: usize) {
+    for r in robots.iter_mut() {
+        let (x, y) = future_pos(r, t, x_limit, y_limit);
+        r.x = x as i64;
+        r.y = y as i64;
+    }
+}
+
+/// The room with `#` wherever at least one robot is
+pub fn robot_grid(robots: &[Robot], x_limit: usize, y_limit: usize) -> Vec<Vec<char>> {
+    let mut grid = vec![vec!['.'; x_limit]; y_limit];
+    for r in robots {
+        grid[r.y as usize][r.x as usize] = '#';
+    }
+
+    grid
+}
+
+/// Robots in their room, for [`robot_repl`]
+#[derive(Clone)]
+pub struct RobotSim {
+    robots: Vec<Robot>,
+    x_limit: usize,
+    y_limit: usize,
+}
+
+impl Simulation for RobotSim {
+    fn step(&mut self, n: usize) -> usize {
+        step_robots(&mut self.robots, n, self.x_limit, self.y_limit);
+        n
+    }
+
+    fn render(&self) -> String {
+        robot_grid(&self.robots, self.x_limit, self.y_limit)
+            .into_iter()
+            .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+            .collect()
+    }
+}
+
+/// Part 2: step the robots interactively on stdin/stdout until they draw a tree,
+/// returning the elapsed time
+pub fn robot_repl(robots: Vec<Robot>, x_limit: usize, y_limit: usize) -> usize {
+    let sim = RobotSim {
+        robots,
+        x_limit,
+        y_limit,
+    };
+    let (_, t) = Repl::new(sim).run(io::stdin().lock(), io::stdout());
+
+    t
+}
@@ -0,0 +1,13 @@
+common::parser_fuzz_test!(
+    day14::parse,
+    seed = 14,
+    inputs = ["input", "test"],
+    // Inputs that once made the parser panic
+    regressions = [
+        b"p=0,4 v=3,-3\np=6,3 v=-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,0 v=1,3\np=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\np=9,3 v=2,3\np=7,3 v=-199999999999999999999999,2\np=2,4 v=2,-3\np=9,5 v=-3,-3\n",
+        b"p0=,4",
+        b"p=0,4 v=3,-3\np=6,3 v=,0-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,0",
+        b"p=0,4 v=3,-3\np=6,3 v=18446744073709551615-1,-3\np=10,3 v=-1,2\np=2,0 v=2,-1\np=0,01,3\np=3,0 v=-2,-2\np=7,6 v=-1,-3\np=3,0 v=-1,-2\np=9,3 v=2,3\np=7,3 v=-1,2\np=2,4 v=2,-3\np=9,5 v=-3,-3\n",
+        b"p=0,4 v=3,[-3\np=6",
+    ],
+);
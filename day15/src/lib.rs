@@ -0,0 +1,465 @@
+//! Day 15: Warehouse Woes
+
+use std::{
+    io::{self, BufRead},
+    path::Path,
+};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+    repl::{Repl, Simulation},
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 15);
+
+common::embed_inputs!("input", "test");
+
+const BOX: char = 'O';
+
+/// The map lines and the move lines of `filename`
+pub fn read_inputs<P>(
+    filename: P,
+) -> (
+    impl IntoIterator<Item = String>,
+    impl IntoIterator<Item = String>,
+)
+where
+    P: AsRef<Path>,
+{
+    lines_to_inputs(
+        input::open(PUZZLE, filename, EMBEDDED)
+            .lines()
+            .map(|l| l.unwrap()),
+    )
+}
+
+/// Split `lines` into the map lines and the move lines, either is empty if missing
+pub fn lines_to_inputs(lines: impl Iterator<Item = String>) -> (Vec<String>, Vec<String>) {
+    let lines: Vec<_> = lines.collect();
+
+    let mut r_m = lines
+        .split(|l| l.is_empty())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned());
+
+    (
+        r_m.next().unwrap_or_default(),
+        r_m.next().unwrap_or_default(),
+    )
+}
+
+/// Map rows, without the border wall. The map must be walled in and hold one robot.
+pub fn lines_to_grid(
+    lines: impl IntoIterator<Item = String>,
+) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid = parse::grid(lines, |c| "#.O@".contains(c))?;
+
+    let (width, height) = (grid[0].len(), grid.len());
+    let walled = grid.iter().enumerate().all(|(y, row)| {
+        row.iter()
+            .enumerate()
+            .all(|(x, &c)| c == '#' || (x > 0 && y > 0 && x < width - 1 && y < height - 1))
+    });
+    if !walled || width < 3 || height < 3 {
+        return Err(ParseError::new("map is not surrounded by walls"));
+    }
+    let robots = grid.iter().flatten().filter(|&&c| c == '@').count();
+    if robots != 1 {
+        return Err(ParseError::new(format!(
+            "expected one robot, found {}",
+            robots
+        )));
+    }
+
+    // Drop border from grid
+    Ok(grid[1..height - 1]
+        .iter()
+        .map(|row| row[1..width - 1].to_vec())
+        .collect())
+}
+
+/// Double the width of a grid from [`lines_to_grid`], boxes become `[]`
+pub fn widen_grid(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .flat_map(|&c| match c {
+                    'O' => ['[', ']'],
+                    '@' => ['@', '.'],
+                    _ => [c, c],
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn robot_pos<R>(grid: &[R]) -> (usize, usize)
+where
+    R: AsRef<[char]>,
+{
+    grid.iter()
+        .enumerate()
+        .find_map(|(y, r)| {
+            r.as_ref()
+                .iter()
+                .copied()
+                .enumerate()
+                .find(|(_, c)| *c == '@')
+                .map(|(x, _)| (x, y))
+        })
+        .unwrap()
+}
+
+/// Move the robot one step, pushing any boxes in the way. Returns the new robot position.
+pub fn robot_step<R>(
+    grid: &mut [R],
+    robot_pos: (usize, usize),
+    dx: isize,
+    dy: isize,
+) -> (usize, usize)
+where
+    R: AsMut<[char]> + AsRef<[char]>,
+{
+    let (x, y) = robot_pos;
+    let (nx, ny) = (x as isize + dx, y as isize + dy);
+
+    let mut push_end_x = nx;
+    let mut push_end_y = ny;
+    while push_end_x >= 0
+        && push_end_y >= 0
+        && push_end_y < grid.len() as isize
+        && push_end_x < grid[push_end_y as usize].as_ref().len() as isize
+        && grid[push_end_y as usize].as_ref()[push_end_x as usize] == BOX
+    {
+        push_end_x += dx;
+        push_end_y += dy;
+    }
+
+    if push_end_x < 0
+        || push_end_y < 0
+        || push_end_y >= grid.len() as isize
+        || push_end_x >= grid[push_end_y as usize].as_ref().len() as isize
+        || grid[push_end_y as usize].as_ref()[push_end_x as usize] != '.'
+    {
+        return (x, y);
+    }
+
+    let nx = nx as usize;
+    let ny = ny as usize;
+
+    grid[ny].as_mut()[nx] = '@';
+    grid[y].as_mut()[x] = '.';
+
+    if push_end_x != nx as isize || push_end_y != ny as isize {
+        grid[push_end_y as usize].as_mut()[push_end_x as usize] = BOX;
+    }
+
+    (nx, ny)
+}
+
+/// [`robot_step`] in the double width warehouse
+pub fn robot_step_wide<R>(
+    grid: &mut [R],
+    robot_pos: (usize, usize),
+    dx: isize,
+    dy: isize,
+) -> (usize, usize)
+where
+    R: AsMut<[char]> + AsRef<[char]>,
+{
+    let (x, y) = robot_pos;
+    let (nx, ny) = (x as isize + dx, y as isize + dy);
+
+    if robot_push_wide(grid, (nx, ny), dx, dy, false) {
+        robot_push_wide(grid, (nx, ny), dx, dy, true);
+        let (nx, ny) = (nx as usize, ny as usize);
+        grid[ny].as_mut()[nx] = '@';
+        grid[y].as_mut()[x] = '.';
+
+        (nx, ny)
+    } else {
+        (x, y)
+    }
+}
+
+fn robot_push_wide<R>(
+    grid: &mut [R],
+    pushed_into: (isize, isize),
+    dx: isize,
+    dy: isize,
+    do_push: bool,
+) -> bool
+where
+    R: AsMut<[char]> + AsRef<[char]>,
+{
+    let (x, y) = pushed_into;
+
+    if x < 0 || y < 0 || y >= grid.len() as isize || x >= grid[y as usize].as_ref().len() as isize {
+        return false;
+    }
+    let (x, y) = (x as usize, y as usize);
+
+    if dx == 0 {
+        if grid[y].as_ref()[x] == '.' {
+            return true;
+        }
+
+        if grid[y].as_ref()[x] == '#' {
+            return false;
+        }
+
+        // Must be a box
+        let x2 = if grid[y].as_ref()[x] == '[' {
+            x + 1
+        } else {
+            x - 1
+        };
+
+        let (nx, ny) = (x as isize, y as isize + dy);
+        let nx2 = x2 as isize;
+        if robot_push_wide(grid, (nx, ny), dx, dy, do_push)
+            && robot_push_wide(grid, (nx2, ny), dx, dy, do_push)
+        {
+            if do_push {
+                grid[ny as usize].as_mut()[nx as usize] = grid[y].as_ref()[x];
+                grid[ny as usize].as_mut()[nx2 as usize] = grid[y].as_ref()[x2];
+                grid[y].as_mut()[x] = '.';
+                grid[y].as_mut()[x2] = '.';
+            }
+            true
+        } else {
+            false
+        }
+    } else {
+        // dy == 0
+        let mut push_end_x = x as isize;
+        while push_end_x >= 0
+            && push_end_x < grid[y].as_ref().len() as isize
+            && (grid[y].as_ref()[push_end_x as usize] == '['
+                || grid[y].as_ref()[push_end_x as usize] == ']')
+        {
+            push_end_x += dx;
+        }
+
+        if push_end_x < 0
+            || push_end_x >= grid[y].as_ref().len() as isize
+            || grid[y].as_ref()[push_end_x as usize] != '.'
+        {
+            false
+        } else {
+            if do_push {
+                while push_end_x != x as isize {
+                    let prev = push_end_x - dx;
+                    grid[y].as_mut()[push_end_x as usize] = grid[y].as_ref()[prev as usize];
+                    push_end_x = prev;
+                }
+            }
+            true
+        }
+    }
+}
+
+fn dir(c: char) -> (isize, isize) {
+    match c {
+        '^' => (0, -1),
+        'v' => (0, 1),
+        '<' => (-1, 0),
+        '>' => (1, 0),
+        _ => panic!("Invalid direction"),
+    }
+}
+
+pub fn execute_robot_run<R>(grid: &mut [R], direction_seq: impl Iterator<Item = char>)
+where
+    R: AsMut<[char]> + AsRef<[char]>,
+{
+    let mut robot_pos = robot_pos(grid);
+    for d in direction_seq {
+        robot_pos = robot_step(grid, robot_pos, dir(d).0, dir(d).1);
+    }
+}
+
+pub fn execute_robot_run_wide<R>(grid: &mut [R], direction_seq: impl Iterator<Item = char>)
+where
+    R: AsMut<[char]> + AsRef<[char]>,
+{
+    let mut robot_pos = robot_pos(grid);
+    for d in direction_seq {
+        robot_pos = robot_step_wide(grid, robot_pos, dir(d).0, dir(d).1);
+    }
+}
+
+/// Robot moves, `<`, `>`, `^` or `v`, across any number of lines
+pub fn lines_to_dirs(lines: impl IntoIterator<Item = String>) -> Result<Vec<char>, ParseError> {
+    let dirs = parse::lines_with(lines, |l| match l.chars().find(|c| !"<>^v".contains(*c)) {
+        Some(c) => Err(ParseError::new(format!("invalid move {:?}", c))),
+        None => Ok(l.chars().collect::<Vec<_>>()),
+    })?;
+
+    Ok(dirs.into_iter().flatten().collect())
+}
+
+/// The warehouse map without its border wall, and the robot's moves
+pub struct Warehouse {
+    pub grid: Vec<Vec<char>>,
+    pub directions: Vec<char>,
+}
+
+/// Parse the map and the moves. Move errors are numbered from the first move line.
+pub fn inputs_to_warehouse(
+    inputs: (
+        impl IntoIterator<Item = String>,
+        impl IntoIterator<Item = String>,
+    ),
+) -> Result<Warehouse, ParseError> {
+    let (grid_lines, dir_lines) = inputs;
+
+    Ok(Warehouse {
+        grid: lines_to_grid(grid_lines)?,
+        directions: lines_to_dirs(dir_lines).map_err(|e| ParseError {
+            msg: format!("moves: {}", e.msg),
+            ..e
+        })?,
+    })
+}
+
+/// [`inputs_to_warehouse`] on the text of an input file
+pub fn parse(text: &str) -> Result<Warehouse, ParseError> {
+    inputs_to_warehouse(lines_to_inputs(text.lines().map(|l| l.to_string())))
+}
+
+/// The warehouse after the robot is done moving
+pub fn final_grid(warehouse: &Warehouse) -> Vec<Vec<char>> {
+    let mut grid = warehouse.grid.clone();
+    execute_robot_run(&mut grid, warehouse.directions.iter().copied());
+
+    grid
+}
+
+/// [`final_grid`] in the double width warehouse
+pub fn final_grid_wide(warehouse: &Warehouse) -> Vec<Vec<char>> {
+    let mut grid = widen_grid(&warehouse.grid);
+    execute_robot_run_wide(&mut grid, warehouse.directions.iter().copied());
+
+    grid
+}
+
+/// Part 1: sum of box GPS coordinates after the robot is done moving
+pub fn gps_sum(warehouse: &Warehouse) -> usize {
+    final_grid(warehouse)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(y, r)| r.into_iter().enumerate().map(move |(x, c)| (x, y, c)))
+        .filter(|(_, _, c)| *c == BOX)
+        .map(|(x, y, _)| (x + 1) + ((y + 1) * 100))
+        .sum()
+}
+
+/// Part 2: [`gps_sum`] in the double width warehouse
+pub fn gps_sum_wide(warehouse: &Warehouse) -> usize {
+    final_grid_wide(warehouse)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(y, r)| r.into_iter().enumerate().map(move |(x, c)| (x, y, c)))
+        .filter(|(_, _, c)| *c == '[')
+        .map(|(x, y, _)| (x + 2) + ((y + 1) * 100))
+        .sum()
+}
+
+/// Move by move run of a [`Warehouse`], for [`warehouse_repl`]
+#[derive(Clone)]
+pub struct WarehouseSim {
+    grid: Vec<Vec<char>>,
+    robot_pos: (usize, usize),
+    directions: Vec<char>,
+    next_direction: usize,
+    wide: bool,
+}
+
+impl WarehouseSim {
+    fn move_robot(&mut self, d: char) {
+        let (dx, dy) = dir(d);
+        self.robot_pos = if self.wide {
+            robot_step_wide(&mut self.grid, self.robot_pos, dx, dy)
+        } else {
+            robot_step(&mut self.grid, self.robot_pos, dx, dy)
+        };
+    }
+}
+
+impl Simulation for WarehouseSim {
+    fn step(&mut self, n: usize) -> usize {
+        let remaining = self.directions.len() - self.next_direction;
+        let n = n.min(remaining);
+        for i in self.next_direction..self.next_direction + n {
+            self.move_robot(self.directions[i]);
+        }
+        self.next_direction += n;
+
+        n
+    }
+
+    fn render(&self) -> String {
+        let width = self.grid.first().map_or(0, |r| r.len());
+        let border = "#".repeat(width + if self.wide { 4 } else { 2 });
+        let side = if self.wide { "##" } else { "#" };
+
+        let mut out = format!("{}\n", border);
+        for row in &self.grid {
+            out.push_str(side);
+            out.extend(row);
+            out.push_str(side);
+            out.push('\n');
+        }
+        out.push_str(&border);
+        out.push('\n');
+
+        let upcoming = self.directions[self.next_direction..]
+            .iter()
+            .take(20)
+            .collect::<String>();
+        out.push_str(&format!(
+            "moves left: {} next: {}\n",
+            self.directions.len() - self.next_direction,
+            upcoming
+        ));
+
+        out
+    }
+}
+
+/// Explore the robot's run interactively on stdin/stdout
+pub fn warehouse_repl(warehouse: &Warehouse, wide: bool) {
+    let grid = if wide {
+        widen_grid(&warehouse.grid)
+    } else {
+        warehouse.grid.clone()
+    };
+    let sim = WarehouseSim {
+        robot_pos: robot_pos(&grid),
+        grid,
+        directions: warehouse.directions.clone(),
+        next_direction: 0,
+        wide,
+    };
+
+    Repl::new(sim)
+        .command(
+            "push",
+            "<moves>",
+            "move the robot with ^v<> outside of the recorded sequence",
+            |warehouse, args| {
+                let moves = args.concat();
+                if let Some(c) = moves.chars().find(|c| !"^v<>".contains(*c)) {
+                    return Err(format!("invalid move: {}", c));
+                }
+                for d in moves.chars() {
+                    warehouse.move_robot(d);
+                }
+                Ok(String::new())
+            },
+        )
+        .run(io::stdin().lock(), io::stdout());
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day15::parse, seed = 15, inputs = ["input", "test"],);
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day16::parse, seed = 16, inputs = ["input", "test"],);
@@ -0,0 +1,279 @@
+//! Day 17: Chronospatial Computer
+//!
+//! Not solved yet, this is still a copy of the day 16 maze solver.
+
+use std::{
+    cmp::Ordering,
+    io::BufRead,
+    iter::{self, repeat_with},
+    path::Path,
+};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 17);
+
+common::embed_inputs!("input", "test");
+
+/// All lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl IntoIterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+}
+
+/// The maze, which must have one start `S` and one end `E`
+pub fn lines_to_grid(
+    lines: impl IntoIterator<Item = String>,
+) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid = parse::grid(lines, |c| "#.SE".contains(c))?;
+
+    for marker in ['S', 'E'] {
+        let count = grid.iter().flatten().filter(|&&c| c == marker).count();
+        if count != 1 {
+            return Err(ParseError::new(format!(
+                "expected one {:?}, found {}",
+                marker, count
+            )));
+        }
+    }
+
+    Ok(grid)
+}
+
+/// [`lines_to_grid`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    lines_to_grid(parse::text_lines(text))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dir {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+fn dir_to_offset(dir: Dir) -> (isize, isize) {
+    match dir {
+        Dir::Up => (0, -1),
+        Dir::Down => (0, 1),
+        Dir::Left => (-1, 0),
+        Dir::Right => (1, 0),
+    }
+}
+
+pub fn dir_to_index(dir: Dir) -> usize {
+    match dir {
+        Dir::Up => 0,
+        Dir::Down => 1,
+        Dir::Left => 2,
+        Dir::Right => 3,
+    }
+}
+
+fn rotation_options(dir: Dir) -> [Dir; 2] {
+    match dir {
+        Dir::Up => [Dir::Left, Dir::Right],
+        Dir::Down => [Dir::Right, Dir::Left],
+        Dir::Left => [Dir::Down, Dir::Up],
+        Dir::Right => [Dir::Up, Dir::Down],
+    }
+}
+
+struct HeapNode((usize, usize, Dir), usize, (usize, usize, Dir));
+
+impl PartialEq for HeapNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.1.eq(&other.1)
+    }
+}
+
+impl Eq for HeapNode {}
+
+impl PartialOrd for HeapNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.1.cmp(&other.1).reverse()
+    }
+}
+
+/// Lowest score from `start` to `end` facing any direction
+pub fn shortest_path_len<R>(
+    grid: &[R],
+    start: (usize, usize, Dir),
+    end: (usize, usize),
+) -> Option<usize>
+where
+    R: AsRef<[char]>,
+{
+    let (dist, _) = dijkstra_dists_and_paths(grid, start, end);
+
+    if let Some(&d) = dist[end.1][end.0].iter().min()
+        && d != usize::MAX
+    {
+        return Some(d);
+    }
+
+    None
+}
+
+/// Lowest score to reach each (x, y, direction), indexed `[y][x][dir_to_index(dir)]`
+pub type Dists = Vec<Vec<[usize; 4]>>;
+
+/// Predecessors on lowest score paths to each (x, y, direction), indexed like [`Dists`]
+pub type Prevs = Vec<Vec<[Vec<(usize, usize, Dir)>; 4]>>;
+
+/// Dijkstra over (x, y, direction) states. Moving forward costs 1, turning 90 degrees costs 1000.
+/// Stops expanding at `end`, recording every lowest score predecessor.
+pub fn dijkstra_dists_and_paths<R>(
+    grid: &[R],
+    start: (usize, usize, Dir),
+    end: (usize, usize),
+) -> (Dists, Prevs)
+where
+    R: AsRef<[char]>,
+{
+    // Three dimensions (x, y, direction)
+    let mut dist = vec![vec![[usize::MAX; 4]; grid[0].as_ref().len()]; grid.len()];
+    let inner_array: [Vec<(usize, usize, Dir)>; 4] = repeat_with(Vec::new)
+        .take(4)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let mut prev = vec![vec![inner_array; grid[0].as_ref().len()]; grid.len()];
+
+    // Heap
+    let mut queue = std::collections::BinaryHeap::new();
+    queue.push(HeapNode(start, 0, start));
+    while let Some(HeapNode((x, y, odir), d, prev_node)) = queue.pop() {
+        let iodir = dir_to_index(odir);
+
+        if d > dist[y][x][iodir] {
+            continue;
+        }
+        // If d was less than the current distance, we would have already visited this node
+        // So d == dist[y][x][iodir] or node is unvisited
+
+        dist[y][x][iodir] = d;
+
+        let visited = !prev[y][x][iodir].is_empty();
+
+        prev[y][x][iodir].push(prev_node);
+
+        // Match (end_x, end_y, any direction)
+        if (x, y) == end || visited {
+            continue;
+        }
+
+        for (dir, cost) in
+            iter::once((odir, 1)).chain(rotation_options(odir).into_iter().map(|d| (d, 1000)))
+        {
+            let (nx, ny) = if odir == dir {
+                // Continue in the same direction
+                let (dx, dy) = dir_to_offset(dir);
+                (x as isize + dx, y as isize + dy)
+            } else {
+                // Rotate, no translation
+                (x as isize, y as isize)
+            };
+
+            if nx < 0
+                || ny < 0
+                || ny >= grid.len() as isize
+                || nx >= grid[ny as usize].as_ref().len() as isize
+            {
+                continue;
+            }
+
+            let (nx, ny) = (nx as usize, ny as usize);
+            if grid[ny].as_ref()[nx] == '#' {
+                continue;
+            }
+
+            if dist[ny][nx][dir_to_index(dir)] > d + cost {
+                queue.push(HeapNode((nx, ny, dir), d + cost, (x, y, odir)));
+            }
+        }
+    }
+
+    (dist, prev)
+}
+
+/// Start (facing right) and end positions
+pub fn get_start_and_end<R>(grid: &[R]) -> ((usize, usize, Dir), (usize, usize))
+where
+    R: AsRef<[char]>,
+{
+    let mut start = None;
+    let mut end = None;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &c) in row.as_ref().iter().enumerate() {
+            if c == 'S' {
+                start = Some((x, y, Dir::Right));
+            } else if c == 'E' {
+                end = Some((x, y));
+            }
+        }
+    }
+
+    (start.unwrap(), end.unwrap())
+}
+
+/// Tiles on any lowest score path from `start` to `end`
+pub fn tiles_in_shortest_paths<R>(
+    grid: &[R],
+    start: (usize, usize, Dir),
+    end: (usize, usize),
+) -> usize
+where
+    R: AsRef<[char]>,
+{
+    let (_, prev) = dijkstra_dists_and_paths(grid, start, end);
+
+    let mut queue = [Dir::Up, Dir::Down, Dir::Left, Dir::Right]
+        .into_iter()
+        .map(|d| (end.0, end.1, d))
+        .collect::<Vec<_>>();
+    let mut visited = vec![vec![false; grid[0].as_ref().len()]; grid.len()];
+
+    while let Some((x, y, dir)) = queue.pop() {
+        visited[y][x] = true;
+
+        if (x, y, dir) == start {
+            continue;
+        }
+
+        for &(px, py, pdir) in &prev[y][x][dir_to_index(dir)] {
+            queue.push((px, py, pdir));
+        }
+    }
+
+    visited.iter().flatten().filter(|&&v| v).count()
+}
+
+/// Part 1: lowest possible score from S to E
+pub fn lowest_path_score(grid: &[Vec<char>]) -> usize {
+    let (start, end) = get_start_and_end(grid);
+
+    shortest_path_len(grid, start, end).unwrap()
+}
+
+/// Part 2: tiles that are part of at least one lowest score path
+pub fn tile_count_in_shortest_paths(grid: &[Vec<char>]) -> usize {
+    let (start, end) = get_start_and_end(grid);
+
+    tiles_in_shortest_paths(grid, start, end)
+}
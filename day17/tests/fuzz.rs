@@ -0,0 +1 @@
+common::parser_fuzz_test!(day17::parse, seed = 17, inputs = ["input", "test"],);
@@ -0,0 +1,659 @@
+//! Day 2: Red-Nosed Reports
+
+use std::{cmp::Reverse, collections::VecDeque, io::BufRead, ops::RangeInclusive, path::Path};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 2);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// Parse a whitespace separated report
+pub fn line_to_levels(line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split_ascii_whitespace().map(parse::number).collect()
+}
+
+pub fn lines_to_reports(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<u64>>, ParseError> {
+    parse::lines_with(lines, line_to_levels)
+}
+
+/// [`lines_to_reports`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<u64>>, ParseError> {
+    lines_to_reports(parse::text_lines(text))
+}
+
+/// Part 1: reports that are safe under [`SafetyPolicy::PART_1`]
+pub fn safe_report_count(reports: &[Vec<u64>]) -> usize {
+    safe_report_count_with(reports, &SafetyPolicy::PART_1)
+}
+
+/// Part 2: reports that are safe under [`SafetyPolicy::PART_2`]
+pub fn safe_report_count_2(reports: &[Vec<u64>]) -> usize {
+    safe_report_count_with(reports, &SafetyPolicy::PART_2)
+}
+
+pub fn safe_report_count_with(reports: &[Vec<u64>], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|levels| policy.is_safe(levels))
+        .count()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// Either direction, as long as the whole report keeps to it
+    Either,
+}
+
+/// What makes a report safe: every step between neighbouring levels is in
+/// `min_step..=max_step` and goes in `direction`, after removing at most
+/// `max_removals` levels. The step range is never empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    min_step: u64,
+    max_step: u64,
+    pub direction: Direction,
+    pub max_removals: usize,
+}
+
+impl SafetyPolicy {
+    /// Strictly increasing or decreasing, by steps of 1 to 3
+    pub const PART_1: SafetyPolicy = SafetyPolicy {
+        min_step: 1,
+        max_step: 3,
+        direction: Direction::Either,
+        max_removals: 0,
+    };
+
+    /// [`SafetyPolicy::PART_1`] with the Problem Dampener removing one level
+    pub const PART_2: SafetyPolicy = SafetyPolicy {
+        max_removals: 1,
+        ..Self::PART_1
+    };
+
+    /// Steps of `min_step..=max_step`, `None` if that range is empty
+    pub fn with_steps(self, min_step: u64, max_step: u64) -> Option<Self> {
+        (min_step <= max_step).then_some(SafetyPolicy {
+            min_step,
+            max_step,
+            ..self
+        })
+    }
+
+    pub fn steps(&self) -> RangeInclusive<u64> {
+        self.min_step..=self.max_step
+    }
+
+    pub fn with_direction(self, direction: Direction) -> Self {
+        SafetyPolicy { direction, ..self }
+    }
+
+    pub fn with_max_removals(self, max_removals: usize) -> Self {
+        SafetyPolicy {
+            max_removals,
+            ..self
+        }
+    }
+
+    pub fn is_safe(&self, levels: &[u64]) -> bool {
+        self.removals_needed(levels).is_some()
+    }
+
+    /// Fewest levels to remove to make the report safe, `None` if that takes
+    /// more than `max_removals`
+    pub fn removals_needed(&self, levels: &[u64]) -> Option<usize> {
+        self.directions()
+            .iter()
+            .filter_map(|&dir| self.removals_needed_in(levels, dir))
+            .min()
+    }
+
+    /// Indices of the longest subsequence of `levels` that is safe without any
+    /// removal, so the fewest removals ignoring `max_removals` are the rest.
+    ///
+    /// Like a longest increasing subsequence, `longest[i]` is the longest safe
+    /// subsequence ending at level `i`, extended from any earlier level that can
+    /// step to it. O(n²) per direction.
+    pub fn safe_subsequence(&self, levels: &[u64]) -> Vec<usize> {
+        self.directions()
+            .iter()
+            .map(|&dir| {
+                let mut longest = vec![1; levels.len()];
+                let mut prev = vec![None; levels.len()];
+                for i in 0..levels.len() {
+                    for j in 0..i {
+                        if longest[j] + 1 > longest[i] && self.step_ok(levels[j], levels[i], dir) {
+                            longest[i] = longest[j] + 1;
+                            prev[i] = Some(j);
+                        }
+                    }
+                }
+
+                let mut kept = Vec::new();
+                let mut at = (0..levels.len()).max_by_key(|&i| (longest[i], Reverse(i)));
+                while let Some(i) = at {
+                    kept.push(i);
+                    at = prev[i];
+                }
+                kept.reverse();
+                kept
+            })
+            .max_by_key(|kept| kept.len())
+            .unwrap_or_default()
+    }
+
+    /// Adjust levels instead of removing them until the report is safe without
+    /// any removal, minimizing `cost`. Levels stay non-negative.
+    ///
+    /// Meant for reports of a few dozen levels at most: for n levels
+    /// [`RepairCost::TotalChange`] considers O(n³) candidate values and keeps
+    /// O(n⁴) back pointers, [`RepairCost::ChangedLevels`] takes O(n²) time.
+    pub fn repair(&self, levels: &[u64], cost: RepairCost) -> Repair {
+        self.directions()
+            .iter()
+            .map(|&dir| {
+                // A decreasing report read backwards is an increasing one
+                let mut levels = levels.to_vec();
+                if dir == Direction::Decreasing {
+                    levels.reverse();
+                }
+                let mut repaired = match cost {
+                    RepairCost::TotalChange => self.repair_total_change(&levels),
+                    RepairCost::ChangedLevels => self.repair_changed_levels(&levels),
+                };
+                if dir == Direction::Decreasing {
+                    repaired.reverse();
+                }
+                repaired
+            })
+            .map(|repaired| Repair::new(levels, repaired))
+            .min_by_key(|r| match cost {
+                RepairCost::TotalChange => (r.total_change, r.changed as u64),
+                RepairCost::ChangedLevels => (r.changed as u64, r.total_change),
+            })
+            .unwrap()
+    }
+
+    /// Increasing repair with the least total change. In an optimal repair every
+    /// level is an original level, or 0, moved by a whole number of min and max
+    /// steps, so the DP only considers those values. Its predecessors form a
+    /// window of the sorted candidates, kept in a monotone deque.
+    fn repair_total_change(&self, levels: &[u64]) -> Vec<u64> {
+        let Some(&first) = levels.first() else {
+            return Vec::new();
+        };
+
+        let n = levels.len() as u64;
+        let mut candidates = Vec::new();
+        for a in 0..n {
+            for b in 0..n - a {
+                let offset = (a * self.min_step).saturating_add(b.saturating_mul(self.max_step));
+                candidates.push(offset);
+                for &level in levels {
+                    candidates.push(level.saturating_add(offset));
+                    candidates.extend(level.checked_sub(offset));
+                }
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Least change up to the current level ending at each candidate, None
+        // where it cannot end. Wide enough not to overflow.
+        let mut cost = candidates
+            .iter()
+            .map(|&v| Some(v.abs_diff(first) as u128))
+            .collect::<Vec<_>>();
+        let mut from = Vec::new();
+        for &level in &levels[1..] {
+            let mut next = vec![None; candidates.len()];
+            let mut prev = vec![0; candidates.len()];
+            let mut window = VecDeque::<usize>::new();
+            let mut added = 0;
+            for (i, &v) in candidates.iter().enumerate() {
+                // Predecessors lie in v - max_step..=v - min_step
+                while added < candidates.len()
+                    && v.checked_sub(self.min_step)
+                        .is_some_and(|top| candidates[added] <= top)
+                {
+                    if cost[added].is_some() {
+                        while window.back().is_some_and(|&b| cost[b] >= cost[added]) {
+                            window.pop_back();
+                        }
+                        window.push_back(added);
+                    }
+                    added += 1;
+                }
+                while let Some(&oldest) = window.front()
+                    && candidates[oldest] < v.saturating_sub(self.max_step)
+                {
+                    window.pop_front();
+                }
+
+                if let Some(&best) = window.front() {
+                    next[i] = cost[best].map(|c| c + v.abs_diff(level) as u128);
+                    prev[i] = best;
+                }
+            }
+            cost = next;
+            from.push(prev);
+        }
+
+        let mut at = (0..candidates.len())
+            .filter(|&i| cost[i].is_some())
+            .min_by_key(|&i| cost[i])
+            .unwrap();
+        let mut repaired = vec![candidates[at]];
+        for prev in from.iter().rev() {
+            at = prev[at];
+            repaired.push(candidates[at]);
+        }
+        repaired.reverse();
+        repaired
+    }
+
+    /// Increasing repair changing the fewest levels. The unchanged levels form the
+    /// longest chain in which each pair can be bridged by steps in range, as in
+    /// [`SafetyPolicy::safe_subsequence`], and the levels between are filled in.
+    fn repair_changed_levels(&self, levels: &[u64]) -> Vec<u64> {
+        let bridgeable = |i: usize, j: usize| {
+            let steps = (j - i) as u64;
+            levels[j].checked_sub(levels[i]).is_some_and(|rise| {
+                rise >= steps.saturating_mul(self.min_step)
+                    && rise <= steps.saturating_mul(self.max_step)
+            })
+        };
+        // The levels before the first unchanged one must stay non-negative
+        let can_start = |i: usize| levels[i] >= (i as u64).saturating_mul(self.min_step);
+
+        let mut longest = vec![0; levels.len()];
+        let mut prev = vec![None; levels.len()];
+        for i in 0..levels.len() {
+            if can_start(i) {
+                longest[i] = 1;
+            }
+            for j in 0..i {
+                if longest[j] > 0 && longest[j] + 1 > longest[i] && bridgeable(j, i) {
+                    longest[i] = longest[j] + 1;
+                    prev[i] = Some(j);
+                }
+            }
+        }
+
+        let mut kept = Vec::new();
+        let mut at = (0..levels.len()).max_by_key(|&i| (longest[i], Reverse(i)));
+        while let Some(i) = at {
+            kept.push(i);
+            at = prev[i];
+        }
+        kept.reverse();
+        let Some(&first) = kept.first() else {
+            return Vec::new();
+        };
+
+        let mut repaired = levels.to_vec();
+        for (i, level) in repaired[..first].iter_mut().enumerate() {
+            *level = levels[first] - (first - i) as u64 * self.min_step;
+        }
+        for w in kept.windows(2) {
+            // Minimum steps, with the rest of the rise spread from the left
+            let mut extra = levels[w[1]] - levels[w[0]] - (w[1] - w[0]) as u64 * self.min_step;
+            for i in w[0] + 1..w[1] {
+                let step = self.min_step + extra.min(self.max_step - self.min_step);
+                extra -= step - self.min_step;
+                repaired[i] = repaired[i - 1] + step;
+            }
+        }
+        for i in kept[kept.len() - 1] + 1..levels.len() {
+            repaired[i] = repaired[i - 1].saturating_add(self.min_step);
+        }
+
+        repaired
+    }
+
+    fn directions(&self) -> &'static [Direction] {
+        match self.direction {
+            Direction::Either => &[Direction::Increasing, Direction::Decreasing],
+            Direction::Increasing => &[Direction::Increasing],
+            Direction::Decreasing => &[Direction::Decreasing],
+        }
+    }
+
+    /// Whether `b` may directly follow `a`
+    fn step_ok(&self, a: u64, b: u64, dir: Direction) -> bool {
+        let step = match dir {
+            Direction::Increasing if b >= a => b - a,
+            Direction::Decreasing if a >= b => a - b,
+            _ => return false,
+        };
+        self.steps().contains(&step)
+    }
+
+    /// `removed[i]` is the fewest removals from `levels[..=i]` that keep level `i`
+    /// and leave a safe report. A kept level's predecessor is at most
+    /// `max_removals + 1` back, so each level only looks that far.
+    fn removals_needed_in(&self, levels: &[u64], dir: Direction) -> Option<usize> {
+        // No report needs more removals than it has levels
+        let k = self.max_removals.min(levels.len());
+        let Some(last) = levels.len().checked_sub(1) else {
+            return Some(0);
+        };
+
+        let mut removed = vec![None; levels.len()];
+        for i in 0..levels.len() {
+            let mut best = (i <= k).then_some(i);
+            for j in i.saturating_sub(k + 1)..i {
+                if let Some(r) = removed[j]
+                    && self.step_ok(levels[j], levels[i], dir)
+                {
+                    let r = r + i - j - 1;
+                    best = Some(best.map_or(r, |b: usize| b.min(r)));
+                }
+            }
+            removed[i] = best.filter(|&r| r <= k);
+        }
+
+        (last.saturating_sub(k)..=last)
+            .filter_map(|i| removed[i].map(|r| r + last - i))
+            .filter(|&r| r <= k)
+            .min()
+    }
+}
+
+/// Strictly increasing or decreasing, by steps of 1 to 3
+pub fn level_safe(levels: impl Iterator<Item = u64>) -> bool {
+    let mut levels = levels.peekable();
+    let mut sign = None;
+    while let Some(level) = levels.next() {
+        if let Some(next_level) = levels.peek() {
+            if level == *next_level {
+                return false;
+            }
+            let diff = level as i64 - *next_level as i64;
+            if sign.is_none() {
+                sign = Some(diff.signum());
+            }
+            let Some(sign) = sign else {
+                panic!("sign not set");
+            };
+            let dir_diff = diff * sign;
+            if !(0..=3).contains(&dir_diff) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// [`level_safe`] after removing at most one level, in a single pass.
+///
+/// For each direction it tracks whether the prefix ending at the current level,
+/// with that level kept, is safe with no removal and with at most one. A level
+/// with a removal behind it follows either the previous level, or the one before
+/// that with the previous level removed.
+pub fn level_safe_2(levels: &[u64]) -> bool {
+    if levels.len() < 3 {
+        return true;
+    }
+
+    [Direction::Increasing, Direction::Decreasing]
+        .into_iter()
+        .any(|dir| {
+            let ok = |a, b| SafetyPolicy::PART_1.step_ok(a, b, dir);
+
+            // Whether the prefix up to levels[i - 2] and levels[i - 1] is safe
+            // without a removal, and up to levels[i - 1] with at most one. Up to
+            // levels[1] it always is with one, by removing levels[0].
+            let (mut clean_2, mut clean_1, mut one_1) = (true, ok(levels[0], levels[1]), true);
+            for i in 2..levels.len() {
+                let clean = clean_1 && ok(levels[i - 1], levels[i]);
+                let one = (one_1 && ok(levels[i - 1], levels[i]))
+                    || (clean_2 && ok(levels[i - 2], levels[i]));
+                (clean_2, clean_1, one_1) = (clean_1, clean, one);
+            }
+
+            // Or remove the last level
+            one_1 || clean_2
+        })
+}
+
+/// Fewest levels to drop from each report to make it safe under the part 1 rules,
+/// with the levels that survive
+pub fn min_removals(levels: &[u64]) -> (usize, Vec<u64>) {
+    let kept = SafetyPolicy::PART_1.safe_subsequence(levels);
+    (
+        levels.len() - kept.len(),
+        kept.into_iter().map(|i| levels[i]).collect(),
+    )
+}
+
+/// What [`SafetyPolicy::repair`] minimizes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RepairCost {
+    /// Sum of the absolute changes to every level
+    TotalChange,
+    /// Number of levels changed
+    ChangedLevels,
+}
+
+/// A report with adjusted levels
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repair {
+    pub levels: Vec<u64>,
+    pub total_change: u64,
+    pub changed: usize,
+}
+
+impl Repair {
+    fn new(original: &[u64], levels: Vec<u64>) -> Self {
+        let changes = original.iter().zip(&levels).map(|(a, b)| a.abs_diff(*b));
+        Repair {
+            total_change: changes.clone().fold(0, u64::saturating_add),
+            changed: changes.filter(|&c| c > 0).count(),
+            levels,
+        }
+    }
+}
+
+/// [`SafetyPolicy::repair`] under the part 1 rules, so [`level_safe`] accepts the result
+pub fn repair(levels: &[u64], cost: RepairCost) -> Repair {
+    SafetyPolicy::PART_1.repair(levels, cost)
+}
+
+/// How many reports need each number of removals, indexed by removals
+pub fn removal_histogram(reports: &[Vec<u64>]) -> Vec<usize> {
+    let mut histogram = Vec::new();
+    for levels in reports {
+        let (removed, _) = min_removals(levels);
+        if histogram.len() <= removed {
+            histogram.resize(removed + 1, 0);
+        }
+        histogram[removed] += 1;
+    }
+
+    histogram
+}
+
+/// The [`level_safe`] rule a pair of neighbouring levels breaks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rule {
+    EqualNeighbours,
+    StepTooLarge,
+    /// Goes the other way from the first step
+    DirectionChange,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Indices of the two levels
+    pub pair: (usize, usize),
+    pub rule: Rule,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the Problem Dampener removes the level at `removed`
+    Dampened {
+        violation: Violation,
+        removed: usize,
+    },
+    Unsafe(Violation),
+}
+
+/// The first pair of neighbouring levels that breaks the [`level_safe`] rules
+pub fn first_violation(levels: &[u64]) -> Option<Violation> {
+    let sign = levels.get(1)?.cmp(&levels[0]);
+
+    levels.windows(2).enumerate().find_map(|(i, w)| {
+        let rule = if w[0] == w[1] {
+            Rule::EqualNeighbours
+        } else if w[1].cmp(&w[0]) != sign {
+            Rule::DirectionChange
+        } else if w[0].abs_diff(w[1]) > 3 {
+            Rule::StepTooLarge
+        } else {
+            return None;
+        };
+        Some(Violation {
+            pair: (i, i + 1),
+            rule,
+        })
+    })
+}
+
+/// Why a report is safe or not. A removal that makes it safe has to break up the
+/// first violation or change the direction set by the first step, so only the
+/// first level and the levels around the violation are tried.
+pub fn diagnose(levels: &[u64]) -> Verdict {
+    let Some(violation) = first_violation(levels) else {
+        return Verdict::Safe;
+    };
+
+    let (i, j) = violation.pair;
+    let mut candidates = vec![0, i.saturating_sub(1), i, j];
+    candidates.dedup();
+    let removed = candidates.into_iter().find(|&skip| {
+        let rest = levels
+            .iter()
+            .enumerate()
+            .filter(|(k, _)| *k != skip)
+            .map(|(_, l)| *l);
+        level_safe(rest)
+    });
+
+    match removed {
+        Some(removed) => Verdict::Dampened { violation, removed },
+        None => Verdict::Unsafe(violation),
+    }
+}
+
+impl Rule {
+    fn name(&self) -> &'static str {
+        match self {
+            Rule::EqualNeighbours => "equal neighbours",
+            Rule::StepTooLarge => "step too large",
+            Rule::DirectionChange => "direction change",
+        }
+    }
+}
+
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Safe => "safe",
+            Verdict::Dampened { .. } => "dampened",
+            Verdict::Unsafe(_) => "unsafe",
+        }
+    }
+
+    fn violation(&self) -> Option<&Violation> {
+        match self {
+            Verdict::Safe => None,
+            Verdict::Dampened { violation, .. } | Verdict::Unsafe(violation) => Some(violation),
+        }
+    }
+
+    fn removed(&self) -> Option<usize> {
+        match self {
+            Verdict::Dampened { removed, .. } => Some(*removed),
+            _ => None,
+        }
+    }
+}
+
+/// One row per report, indices are 0-based positions in the report
+pub fn diagnostics_table(reports: &[Vec<u64>]) -> String {
+    let mut table = format!(
+        "{:>5}  {:<8}  {:<7}  {:<16}  {:>7}  levels\n",
+        "line", "verdict", "pair", "rule", "removed"
+    );
+    for (line, levels) in reports.iter().enumerate() {
+        let verdict = diagnose(levels);
+        let violation = verdict.violation();
+        table += &format!(
+            "{:>5}  {:<8}  {:<7}  {:<16}  {:>7}  {}\n",
+            line + 1,
+            verdict.name(),
+            violation.map_or("-".to_string(), |v| format!("{},{}", v.pair.0, v.pair.1)),
+            violation.map_or("-", |v| v.rule.name()),
+            verdict.removed().map_or("-".to_string(), |r| r.to_string()),
+            levels
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+    }
+
+    table
+}
+
+/// [`diagnostics_table`] as a JSON array of objects, one per report
+pub fn diagnostics_json(reports: &[Vec<u64>]) -> String {
+    let rows = reports
+        .iter()
+        .enumerate()
+        .map(|(line, levels)| {
+            let verdict = diagnose(levels);
+            let mut row = format!(
+                "{{\"line\":{},\"levels\":{:?},\"verdict\":\"{}\"",
+                line + 1,
+                levels,
+                verdict.name()
+            );
+            if let Some(v) = verdict.violation() {
+                row += &format!(
+                    ",\"pair\":[{},{}],\"rule\":\"{}\"",
+                    v.pair.0,
+                    v.pair.1,
+                    v.rule.name()
+                );
+            }
+            if let Some(removed) = verdict.removed() {
+                row += &format!(",\"removed\":{}", removed);
+            }
+            row + "}"
+        })
+        .collect::<Vec<_>>();
+
+    format!("[\n  {}\n]\n", rows.join(",\n  "))
+}
@@ -1,110 +1,101 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    path::Path,
+use common::{
+    report::{self, DayRecord, PartResult, timed},
+    submit::{GuessStore, Submission},
+};
+use day2::{
+    PUZZLE, RepairCost, diagnostics_json, diagnostics_table, lines_to_reports, read_lines,
+    removal_histogram, repair, safe_report_count, safe_report_count_2,
 };
 
 fn main() {
-    let lines = read_lines("input");
-    let cnt = safe_report_count(lines);
-    println!("safe reports: {}", cnt);
-
-    let lines = read_lines("input");
-    let cnt = safe_report_count_2(lines);
-    println!("safe reports 2: {}", cnt);
-}
+    if std::env::args().nth(1).as_deref() == Some("diagnose") {
+        let json = std::env::args().any(|a| a == "--json");
+        let filename = std::env::args()
+            .skip(2)
+            .find(|a| a != "--json")
+            .unwrap_or("input".to_string());
+        let reports = lines_to_reports(read_lines(filename)).unwrap();
 
-fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename).unwrap();
-    io::BufReader::new(file)
-        .lines()
-        .map(|l| l.unwrap())
-        .filter(|l| !l.trim().is_empty())
-}
-
-fn line_to_levels(line: &str) -> Vec<u64> {
-    line.split_ascii_whitespace()
-        .map(|e| e.parse().unwrap())
-        .collect::<Vec<_>>()
-}
-
-fn safe_report_count(lines: impl Iterator<Item = String>) -> usize {
-    lines
-        .map(|l| line_to_levels(&l))
-        .filter(|levels| level_safe(levels.iter().copied()))
-        .count()
-}
+        if json {
+            print!("{}", diagnostics_json(&reports));
+        } else {
+            print!("{}", diagnostics_table(&reports));
+        }
+        return;
+    }
 
-fn safe_report_count_2(lines: impl Iterator<Item = String>) -> usize {
-    lines
-        .map(|l| line_to_levels(&l))
-        .filter(|levels| level_safe_2(levels))
-        .count()
-}
+    if std::env::args().nth(1).as_deref() == Some("removals") {
+        let filename = std::env::args().nth(2).unwrap_or("input".to_string());
+        let reports = lines_to_reports(read_lines(filename)).unwrap();
 
-fn level_safe(levels: impl Iterator<Item = u64>) -> bool {
-    let mut levels = levels.peekable();
-    let mut sign = None;
-    while let Some(level) = levels.next() {
-        if let Some(next_level) = levels.peek() {
-            if level == *next_level {
-                return false;
-            }
-            let diff = level as i64 - *next_level as i64;
-            if sign == None {
-                sign = Some(diff.signum());
-            }
-            let Some(sign) = sign else {
-                panic!("sign not set");
-            };
-            let dir_diff = diff * sign;
-            if dir_diff < 0 || dir_diff > 3 {
-                return false;
-            }
+        let histogram = removal_histogram(&reports);
+        let widest = histogram.iter().max().copied().unwrap_or(0).max(1);
+        println!("removals  reports");
+        for (removed, &count) in histogram.iter().enumerate() {
+            let bar = "#".repeat((count * 50).div_ceil(widest));
+            println!("{:>8}  {:>7}  {}", removed, count, bar);
         }
+        return;
     }
 
-    true
-}
+    if std::env::args().nth(1).as_deref() == Some("repair") {
+        let cost = match std::env::args().nth(2).as_deref() {
+            None | Some("total") => RepairCost::TotalChange,
+            Some("changes") => RepairCost::ChangedLevels,
+            Some(other) => panic!("unknown repair cost {}, expected total or changes", other),
+        };
+        let filename = std::env::args().nth(3).unwrap_or("input".to_string());
+        let reports = lines_to_reports(read_lines(filename)).unwrap();
 
-fn level_safe_2(levels: &[u64]) -> bool {
-    for skip_num in 0..levels.len() {
-        let passed = || {
-            let mut sign = None;
-            for (i, level) in levels.iter().copied().enumerate() {
-                if i == skip_num {
-                    continue;
-                }
-                let mut skip = 0;
-                if i + 1 == skip_num {
-                    skip = 1;
-                }
-                if let Some(next_level) = levels.get(i + 1 + skip) {
-                    if level == *next_level {
-                        return false;
-                    }
-                    let diff = level as i64 - *next_level as i64;
-                    if sign == None {
-                        sign = Some(diff.signum());
-                    }
-                    let Some(sign) = sign else {
-                        panic!("sign not set");
-                    };
-                    let dir_diff = diff * sign;
-                    if dir_diff < 0 || dir_diff > 3 {
-                        return false;
-                    }
-                }
-            }
-            true
+        let join = |levels: &[u64]| {
+            levels
+                .iter()
+                .map(|l| l.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
         };
-        if passed() {
-            return true;
+        let (mut total_change, mut changed) = (0, 0);
+        println!(
+            "{:>5}  {:>7}  {:>6}  report -> repaired",
+            "line", "changed", "change"
+        );
+        for (line, levels) in reports.iter().enumerate() {
+            let repaired = repair(levels, cost);
+            total_change += repaired.total_change;
+            changed += repaired.changed;
+            if repaired.changed > 0 {
+                println!(
+                    "{:>5}  {:>7}  {:>6}  {} -> {}",
+                    line + 1,
+                    repaired.changed,
+                    repaired.total_change,
+                    join(levels),
+                    join(&repaired.levels)
+                );
+            }
         }
+        println!("{} levels changed by {} in total", changed, total_change);
+        return;
     }
 
-    false
+    let reports = lines_to_reports(read_lines("input")).unwrap();
+    let submit = Submission::from_args(PUZZLE);
+
+    let (safe, time1) = timed(|| safe_report_count(&reports));
+    println!("safe reports: {}", safe);
+    submit.answer(1, safe);
+
+    let (safe_2, time2) = timed(|| safe_report_count_2(&reports));
+    println!("safe reports 2: {}", safe_2);
+    submit.answer(2, safe_2);
+
+    if let Some(path) = report::path_from_args(std::env::args()) {
+        let example = lines_to_reports(read_lines("test")).unwrap();
+        let record = DayRecord::new(PUZZLE, "Red-Nosed Reports")
+            .part(PartResult::new(safe, time1))
+            .part(PartResult::new(safe_2, time2))
+            .example(safe_report_count(&example) == 2 && safe_report_count_2(&example) == 4)
+            .verify(&GuessStore::from_env().unwrap());
+        report::merge(path, record).unwrap();
+    }
 }
@@ -0,0 +1,47 @@
+//! Helpers shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use std::ops::Range;
+
+use ::common::rng::Rng;
+use day2::{Direction, SafetyPolicy};
+
+/// The directions `policy` accepts a report in
+pub fn directions(policy: &SafetyPolicy) -> &'static [Direction] {
+    match policy.direction {
+        Direction::Either => &[Direction::Increasing, Direction::Decreasing],
+        Direction::Increasing => &[Direction::Increasing],
+        Direction::Decreasing => &[Direction::Decreasing],
+    }
+}
+
+/// Whether `a` to `b` goes in `dir` by an allowed amount
+pub fn step_ok(policy: &SafetyPolicy, a: u64, b: u64, dir: Direction) -> bool {
+    let step = match dir {
+        Direction::Increasing if b >= a => b - a,
+        Direction::Decreasing if a >= b => a - b,
+        _ => return false,
+    };
+    policy.steps().contains(&step)
+}
+
+/// Whether `levels` is safe without removing or changing any level
+pub fn safe_as_is(levels: &[u64], policy: &SafetyPolicy) -> bool {
+    directions(policy)
+        .iter()
+        .any(|&dir| levels.windows(2).all(|w| step_ok(policy, w[0], w[1], dir)))
+}
+
+/// A report with a length in `len`, taking small steps around a trend so many
+/// reports are close to safe
+pub fn random_levels(rng: &mut Rng, len: Range<u64>) -> Vec<u64> {
+    let len = rng.range(len) as usize;
+    let trend = rng.below(3) as i64 - 1;
+    let mut level = rng.range(10..20) as i64;
+    (0..len)
+        .map(|_| {
+            level = (level + trend * rng.range(0..5) as i64 + rng.below(5) as i64 - 2).max(0);
+            level as u64
+        })
+        .collect()
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use ::common::rng::Rng;
+use common::random_levels;
+use day2::{SafetyPolicy, level_safe_2, lines_to_reports, read_lines};
+
+/// The dampener check as it was before the single-pass version: try every level
+/// as the removed one and rescan the report
+fn level_safe_2_by_skipping(levels: &[u64]) -> bool {
+    for skip_num in 0..levels.len() {
+        let passed = || {
+            let mut sign = None;
+            for (i, level) in levels.iter().copied().enumerate() {
+                if i == skip_num {
+                    continue;
+                }
+                let mut skip = 0;
+                if i + 1 == skip_num {
+                    skip = 1;
+                }
+                if let Some(next_level) = levels.get(i + 1 + skip) {
+                    if level == *next_level {
+                        return false;
+                    }
+                    let diff = level as i64 - *next_level as i64;
+                    if sign.is_none() {
+                        sign = Some(diff.signum());
+                    }
+                    let Some(sign) = sign else {
+                        panic!("sign not set");
+                    };
+                    let dir_diff = diff * sign;
+                    if !(0..=3).contains(&dir_diff) {
+                        return false;
+                    }
+                }
+            }
+            true
+        };
+        if passed() {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[test]
+fn matches_skipping_on_random_reports() {
+    let mut rng = Rng::new(2);
+    for _ in 0..100_000 {
+        let levels = random_levels(&mut rng, 1..12);
+
+        let expected = level_safe_2_by_skipping(&levels);
+        assert_eq!(level_safe_2(&levels), expected, "{:?}", levels);
+        assert_eq!(
+            SafetyPolicy::PART_2.is_safe(&levels),
+            expected,
+            "{:?}",
+            levels
+        );
+    }
+}
+
+#[test]
+fn matches_skipping_on_input() {
+    for levels in
+        lines_to_reports(read_lines(concat!(env!("CARGO_MANIFEST_DIR"), "/input"))).unwrap()
+    {
+        assert_eq!(
+            level_safe_2(&levels),
+            level_safe_2_by_skipping(&levels),
+            "{:?}",
+            levels
+        );
+    }
+}
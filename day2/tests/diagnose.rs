@@ -0,0 +1,80 @@
+mod common;
+
+use ::common::rng::Rng;
+use common::random_levels;
+use day2::{
+    Verdict, diagnose, diagnostics_json, diagnostics_table, level_safe, level_safe_2,
+    lines_to_reports, read_lines,
+};
+
+/// Every verdict agrees with the part 1 and part 2 checks, and a dampened
+/// report is safe once its `removed` level is gone
+fn check(levels: &[u64]) {
+    match diagnose(levels) {
+        Verdict::Safe => assert!(level_safe(levels.iter().copied()), "{:?}", levels),
+        Verdict::Dampened { violation, removed } => {
+            assert!(!level_safe(levels.iter().copied()), "{:?}", levels);
+            assert!(level_safe_2(levels), "{:?}", levels);
+            assert!(violation.pair.1 < levels.len());
+            let rest = levels
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != removed)
+                .map(|(_, l)| *l);
+            assert!(level_safe(rest), "{:?} without {}", levels, removed);
+        }
+        Verdict::Unsafe(_) => {
+            assert!(!level_safe(levels.iter().copied()), "{:?}", levels);
+            assert!(!level_safe_2(levels), "{:?}", levels);
+        }
+    }
+}
+
+#[test]
+fn verdicts_agree_with_level_safe_2_on_random_reports() {
+    let mut rng = Rng::new(48);
+    for _ in 0..100_000 {
+        let levels = random_levels(&mut rng, 0..10);
+        check(&levels);
+    }
+}
+
+#[test]
+fn verdicts_agree_with_level_safe_2_on_input() {
+    for levels in lines_to_reports(read_lines("input")).unwrap() {
+        check(&levels);
+    }
+}
+
+#[test]
+fn table_on_example() {
+    let reports = lines_to_reports(read_lines("test")).unwrap();
+    assert_eq!(
+        diagnostics_table(&reports),
+        " line  verdict   pair     rule              removed  levels
+    1  safe      -        -                       -  7 6 4 2 1
+    2  unsafe    1,2      step too large          -  1 2 7 8 9
+    3  unsafe    2,3      step too large          -  9 7 6 2 1
+    4  dampened  1,2      direction change        1  1 3 2 4 5
+    5  dampened  2,3      equal neighbours        2  8 6 4 4 1
+    6  safe      -        -                       -  1 3 6 7 9
+"
+    );
+}
+
+#[test]
+fn json_on_example() {
+    let reports = lines_to_reports(read_lines("test")).unwrap();
+    assert_eq!(
+        diagnostics_json(&reports),
+        r#"[
+  {"line":1,"levels":[7, 6, 4, 2, 1],"verdict":"safe"},
+  {"line":2,"levels":[1, 2, 7, 8, 9],"verdict":"unsafe","pair":[1,2],"rule":"step too large"},
+  {"line":3,"levels":[9, 7, 6, 2, 1],"verdict":"unsafe","pair":[2,3],"rule":"step too large"},
+  {"line":4,"levels":[1, 3, 2, 4, 5],"verdict":"dampened","pair":[1,2],"rule":"direction change","removed":1},
+  {"line":5,"levels":[8, 6, 4, 4, 1],"verdict":"dampened","pair":[2,3],"rule":"equal neighbours","removed":2},
+  {"line":6,"levels":[1, 3, 6, 7, 9],"verdict":"safe"}
+]
+"#
+    );
+}
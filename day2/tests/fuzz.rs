@@ -0,0 +1 @@
+common::parser_fuzz_test!(day2::parse, seed = 2, inputs = ["input"],);
@@ -0,0 +1,153 @@
+mod common;
+
+use ::common::rng::Rng;
+use common::{random_levels, safe_as_is};
+use day2::{Direction, SafetyPolicy, level_safe, min_removals};
+
+/// Fewest removals found by trying every subset of levels to keep
+fn removals_by_subsets(levels: &[u64], policy: &SafetyPolicy) -> Option<usize> {
+    (0_u32..1 << levels.len())
+        .filter_map(|mask| {
+            let kept = (0..levels.len())
+                .filter(|i| mask & 1 << i != 0)
+                .map(|i| levels[i])
+                .collect::<Vec<_>>();
+            safe_as_is(&kept, policy).then_some(levels.len() - kept.len())
+        })
+        .min()
+        .filter(|&removed| removed <= policy.max_removals)
+}
+
+fn random_policy(rng: &mut Rng) -> SafetyPolicy {
+    let min_step = rng.below(3);
+    SafetyPolicy::PART_1
+        .with_steps(min_step, min_step + rng.below(4))
+        .unwrap()
+        .with_direction(
+            [
+                Direction::Increasing,
+                Direction::Decreasing,
+                Direction::Either,
+            ][rng.below(3) as usize],
+        )
+        .with_max_removals(rng.below(6) as usize)
+}
+
+#[test]
+fn removals_needed_matches_subsets() {
+    let mut rng = Rng::new(46);
+    for _ in 0..10_000 {
+        let policy = random_policy(&mut rng);
+        let levels = random_levels(&mut rng, 0..11);
+        assert_eq!(
+            policy.removals_needed(&levels),
+            removals_by_subsets(&levels, &policy),
+            "{:?} {:?}",
+            policy,
+            levels
+        );
+    }
+}
+
+#[test]
+fn removals_needed_with_several_removals() {
+    // Two and more removals in a fixed direction, where the window of earlier
+    // levels each level looks back over matters most
+    let mut rng = Rng::new(47);
+    for dir in [Direction::Increasing, Direction::Decreasing] {
+        for max_removals in 2..5 {
+            for _ in 0..1_000 {
+                let policy = SafetyPolicy::PART_1
+                    .with_steps(2, 5)
+                    .unwrap()
+                    .with_direction(dir)
+                    .with_max_removals(max_removals);
+                let levels = random_levels(&mut rng, 0..11);
+                assert_eq!(
+                    policy.removals_needed(&levels),
+                    removals_by_subsets(&levels, &policy),
+                    "{:?} {:?}",
+                    policy,
+                    levels
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn removals_needed_with_unbounded_removals() {
+    let policy = SafetyPolicy::PART_1.with_max_removals(usize::MAX);
+    assert_eq!(policy.removals_needed(&[1, 2, 3, 4]), Some(0));
+    assert_eq!(policy.removals_needed(&[1, 2, 9, 3, 4]), Some(1));
+    assert_eq!(policy.removals_needed(&[]), Some(0));
+
+    let mut rng = Rng::new(53);
+    for _ in 0..1_000 {
+        let levels = random_levels(&mut rng, 0..9);
+        assert_eq!(
+            policy.removals_needed(&levels),
+            removals_by_subsets(&levels, &policy),
+            "{:?}",
+            levels
+        );
+    }
+}
+
+#[test]
+fn safe_subsequence_is_a_longest_safe_one() {
+    let mut rng = Rng::new(49);
+    for _ in 0..5_000 {
+        let policy = random_policy(&mut rng);
+        let levels = random_levels(&mut rng, 0..11);
+        let kept = policy.safe_subsequence(&levels);
+
+        assert!(kept.windows(2).all(|w| w[0] < w[1]), "{:?}", kept);
+        assert!(kept.iter().all(|&i| i < levels.len()));
+        let kept_levels = kept.iter().map(|&i| levels[i]).collect::<Vec<_>>();
+        assert!(
+            safe_as_is(&kept_levels, &policy),
+            "{:?} {:?} kept {:?}",
+            policy,
+            levels,
+            kept
+        );
+
+        let unlimited = policy.with_max_removals(levels.len());
+        assert_eq!(
+            Some(levels.len() - kept.len()),
+            removals_by_subsets(&levels, &unlimited),
+            "{:?} {:?}",
+            policy,
+            levels
+        );
+    }
+}
+
+#[test]
+fn min_removals_matches_subsets() {
+    let mut rng = Rng::new(50);
+    for _ in 0..5_000 {
+        let levels = random_levels(&mut rng, 0..11);
+        let (removed, kept) = min_removals(&levels);
+
+        let unlimited = SafetyPolicy::PART_1.with_max_removals(levels.len());
+        assert_eq!(
+            Some(removed),
+            removals_by_subsets(&levels, &unlimited),
+            "{:?}",
+            levels
+        );
+        assert_eq!(kept.len(), levels.len() - removed);
+        assert!(
+            level_safe(kept.iter().copied()),
+            "{:?} kept {:?}",
+            levels,
+            kept
+        );
+
+        // The survivors appear in the report in order
+        let mut rest = levels.iter();
+        assert!(kept.iter().all(|k| rest.any(|l| l == k)), "{:?}", levels);
+    }
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use ::common::rng::Rng;
+use common::{directions, safe_as_is, step_ok};
+use day2::{Direction, RepairCost, SafetyPolicy};
+
+/// Least cost of a safe report of the same length, trying every value up to
+/// where no optimal repair goes
+fn least_cost_by_values(levels: &[u64], policy: &SafetyPolicy, cost: RepairCost) -> u64 {
+    let Some(&top) = levels.iter().max() else {
+        return 0;
+    };
+    let values = 0..=top + levels.len() as u64 * policy.steps().end();
+    let change = |v: u64, level: u64| match cost {
+        RepairCost::TotalChange => v.abs_diff(level),
+        RepairCost::ChangedLevels => (v != level) as u64,
+    };
+
+    directions(policy)
+        .iter()
+        .map(|&dir| {
+            let mut best = values
+                .clone()
+                .map(|v| change(v, levels[0]))
+                .collect::<Vec<_>>();
+            for &level in &levels[1..] {
+                best = values
+                    .clone()
+                    .map(|v| {
+                        let from = values
+                            .clone()
+                            .filter(|&u| step_ok(policy, u, v, dir))
+                            .map(|u| best[u as usize])
+                            .min();
+                        from.map_or(u64::MAX, |c| c.saturating_add(change(v, level)))
+                    })
+                    .collect();
+            }
+            best.into_iter().min().unwrap()
+        })
+        .min()
+        .unwrap()
+}
+
+fn check(levels: &[u64], policy: &SafetyPolicy) {
+    for cost in [RepairCost::TotalChange, RepairCost::ChangedLevels] {
+        let repair = policy.repair(levels, cost);
+        let context = format!("{:?} {:?} {:?} -> {:?}", policy, cost, levels, repair);
+
+        assert_eq!(repair.levels.len(), levels.len(), "{}", context);
+        assert!(safe_as_is(&repair.levels, policy), "{}", context);
+        let changes = levels
+            .iter()
+            .zip(&repair.levels)
+            .map(|(a, b)| a.abs_diff(*b));
+        assert_eq!(repair.total_change, changes.clone().sum(), "{}", context);
+        assert_eq!(
+            repair.changed,
+            changes.filter(|&c| c > 0).count(),
+            "{}",
+            context
+        );
+
+        let achieved = match cost {
+            RepairCost::TotalChange => repair.total_change,
+            RepairCost::ChangedLevels => repair.changed as u64,
+        };
+        assert_eq!(
+            achieved,
+            least_cost_by_values(levels, policy, cost),
+            "{}",
+            context
+        );
+    }
+}
+
+#[test]
+fn repairs_are_safe_and_least_cost() {
+    let mut rng = Rng::new(50);
+    for _ in 0..3_000 {
+        let min_step = rng.below(3);
+        let policy = SafetyPolicy::PART_1
+            .with_steps(min_step, min_step + rng.below(3))
+            .unwrap()
+            .with_direction(
+                [
+                    Direction::Increasing,
+                    Direction::Decreasing,
+                    Direction::Either,
+                ][rng.below(3) as usize],
+            );
+        let len = rng.below(6) as usize;
+        let levels = (0..len).map(|_| rng.below(12)).collect::<Vec<_>>();
+        check(&levels, &policy);
+    }
+}
+
+#[test]
+fn repairs_with_narrow_steps() {
+    let policy = SafetyPolicy::PART_1.with_steps(1, 2).unwrap();
+    check(&[1, 5, 9], &policy);
+    check(&[9, 5, 1], &policy);
+    check(&[0, 0, 0, 0], &policy);
+}
+
+#[test]
+fn rejects_empty_step_range() {
+    assert_eq!(SafetyPolicy::PART_1.with_steps(3, 2), None);
+    let policy = SafetyPolicy::PART_1.with_steps(2, 2).unwrap();
+    assert_eq!(policy.steps(), 2..=2);
+}
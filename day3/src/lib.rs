@@ -0,0 +1,92 @@
+//! Day 3: Mull It Over
+
+use std::{io::BufRead, path::Path, sync::LazyLock};
+
+use regex::Regex;
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 3);
+
+common::embed_inputs!("input", "test", "test2");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(usize, usize),
+    Do,
+    Dont,
+}
+
+/// Valid `mul(a,b)`, `do()` and `don't()` instructions in the corrupted `line`
+pub fn line_to_instructions(line: &str) -> Vec<Instruction> {
+    static RE: LazyLock<Regex> = std::sync::LazyLock::new(|| {
+        Regex::new(r"(mul\(([0-9]{1,3}),([0-9]{1,3})\)|do\(\)|don't\(\))").unwrap()
+    });
+    RE.captures_iter(line)
+        .map(|cap| {
+            if cap.get(1).unwrap().as_str() == "do()" {
+                Instruction::Do
+            } else if cap.get(1).unwrap().as_str() == "don't()" {
+                Instruction::Dont
+            } else {
+                let a = cap.get(2).unwrap().as_str().parse().unwrap();
+                let b = cap.get(3).unwrap().as_str().parse().unwrap();
+                Instruction::Mul(a, b)
+            }
+        })
+        .collect()
+}
+
+pub fn lines_to_instructions(lines: impl Iterator<Item = String>) -> Vec<Instruction> {
+    lines.flat_map(|l| line_to_instructions(&l)).collect()
+}
+
+/// [`lines_to_instructions`] on the text of an input file. Anything that is not an
+/// instruction is corruption to skip, so this never fails.
+pub fn parse(text: &str) -> Result<Vec<Instruction>, ParseError> {
+    Ok(lines_to_instructions(parse::text_lines(text)))
+}
+
+/// Part 1: sum of every multiplication
+pub fn sum_of_muls(instructions: &[Instruction]) -> usize {
+    instructions
+        .iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(a, b) => a * b,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Part 2: sum of multiplications that aren't disabled by a preceding `don't()`
+pub fn sum_of_muls_w_enables(instructions: &[Instruction]) -> usize {
+    instructions
+        .iter()
+        .fold((true, 0), |(enabled, sum), instruction| match instruction {
+            Instruction::Mul(a, b) => {
+                if enabled {
+                    (true, sum + (a * b))
+                } else {
+                    (false, sum)
+                }
+            }
+            Instruction::Do => (true, sum),
+            Instruction::Dont => (false, sum),
+        })
+        .1
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day3::parse, seed = 3, inputs = ["input"],);
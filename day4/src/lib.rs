@@ -0,0 +1,221 @@
+//! Day 4: Ceres Search
+
+use std::{io::BufRead, path::Path};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 4);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+pub fn lines_to_grid(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<char>>, ParseError> {
+    parse::grid(lines, |c| "XMAS".contains(c))
+}
+
+/// [`lines_to_grid`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    lines_to_grid(parse::text_lines(text))
+}
+
+/// Occurrences of `word` starting at (x, y) in any of the 8 directions
+pub fn word_start_at_position(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> usize {
+    let mut count = 0;
+
+    // Horizontal
+    {
+        let mut found = true;
+        for i in 0..word.len() {
+            if grid[y].len() <= x + i || grid[y][x + i] != word[i] {
+                found = false;
+                break;
+            }
+        }
+        if found {
+            count += 1;
+        }
+    }
+
+    // Horizontal backwards
+    {
+        let mut found = true;
+        for i in 0..word.len() {
+            if x < i || grid[y][x - i] != word[i] {
+                found = false;
+                break;
+            }
+        }
+        if found {
+            count += 1;
+        }
+    }
+
+    // Vertical
+    {
+        let mut found = true;
+        for i in 0..word.len() {
+            if grid.len() <= y + i || grid[y + i][x] != word[i] {
+                found = false;
+                break;
+            }
+        }
+        if found {
+            count += 1;
+        }
+    }
+
+    // Vertical backwards
+    {
+        let mut found = true;
+        for i in 0..word.len() {
+            if y < i || grid[y - i][x] != word[i] {
+                found = false;
+                break;
+            }
+        }
+        if found {
+            count += 1;
+        }
+    }
+
+    // Diagonal 315
+    if diag_315(grid, word, x, y) {
+        count += 1;
+    }
+
+    // Diagonal 45
+    if diag_45(grid, word, x, y) {
+        count += 1;
+    }
+
+    // Diagonal 135
+    if diag_135(grid, word, x, y) {
+        count += 1;
+    }
+
+    // Diagonal 225
+    if diag_225(grid, word, x, y) {
+        count += 1;
+    }
+
+    count
+}
+
+fn diag_315(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> bool {
+    if y >= grid.len() || x >= grid[0].len() {
+        return false;
+    }
+
+    for i in 0..word.len() {
+        if grid.len() <= y + i || grid[y + i].len() <= x + i || grid[y + i][x + i] != word[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn diag_45(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> bool {
+    if y >= grid.len() || x >= grid[0].len() {
+        return false;
+    }
+
+    for i in 0..word.len() {
+        if y < i || grid[y - i].len() <= x + i || grid[y - i][x + i] != word[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn diag_135(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> bool {
+    if y >= grid.len() || x >= grid[0].len() {
+        return false;
+    }
+
+    for i in 0..word.len() {
+        if y < i || x < i || grid[y - i][x - i] != word[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn diag_225(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> bool {
+    if y >= grid.len() || x >= grid[0].len() {
+        return false;
+    }
+
+    for i in 0..word.len() {
+        if grid.len() <= y + i || x < i || grid[y + i][x - i] != word[i] {
+            return false;
+        }
+    }
+    true
+}
+
+/// `word` crossed with itself diagonally, in an X with top left corner at (x, y)
+pub fn x_at_position(grid: &[Vec<char>], word: &[char], x: usize, y: usize) -> bool {
+    // 00 ** ** 30
+    // ** 11 21 **
+    // ** 12 22 **
+    // 03 ** ** 33
+
+    // 315 * * 225
+    //  *  * *  *
+    //  *  * *  *
+    //  45 * * 135
+
+    (diag_315(grid, word, x, y) || diag_135(grid, word, x + word.len() - 1, y + word.len() - 1))
+        && (diag_225(grid, word, x + word.len() - 1, y)
+            || diag_45(grid, word, x, y + word.len() - 1))
+}
+
+/// Part 1: occurrences of `word` in any direction
+pub fn count_word_occurances(grid: &[Vec<char>], word: &str) -> usize {
+    let word = word.chars().collect::<Vec<_>>();
+
+    grid.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map({
+                    let word = &word;
+                    move |(x, _)| word_start_at_position(grid, word, x, y)
+                })
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Part 2: occurrences of two `word`s crossing in an X
+pub fn count_x_occurances(grid: &[Vec<char>], word: &str) -> usize {
+    let word = word.chars().collect::<Vec<_>>();
+
+    grid.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter({
+                    let word = &word;
+                    move |(x, _)| x_at_position(grid, word, *x, y)
+                })
+                .count()
+        })
+        .sum()
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day4::parse, seed = 4, inputs = ["input", "test"],);
@@ -0,0 +1,166 @@
+//! Day 5: Print Queue
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    ops::Div,
+    path::Path,
+};
+
+use common::{
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 5);
+
+common::embed_inputs!("input", "test");
+
+/// All lines of `filename`, including the blank separator
+pub fn read_lines_incl_empty<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+}
+
+fn separate_ordering_and_page_lines(
+    lines: impl Iterator<Item = String>,
+) -> Result<(Vec<String>, Vec<String>), ParseError> {
+    let lines: Vec<String> = lines.collect();
+    let mut ord_and_pg = lines.split(|l| l.is_empty()).take(2);
+
+    match (ord_and_pg.next(), ord_and_pg.next()) {
+        (Some(ordering), Some(pages)) => Ok((ordering.to_vec(), pages.to_vec())),
+        _ => Err(ParseError::new(
+            "expected a blank line between the ordering rules and the updates",
+        )),
+    }
+}
+
+/// Parse a `before|after` ordering rule
+fn line_to_rule(line: &str) -> Result<(u64, u64), ParseError> {
+    let (left, right) = line
+        .split_once('|')
+        .ok_or_else(|| ParseError::new("expected before|after"))?;
+
+    Ok((parse::number(left)?, parse::number(right)?))
+}
+
+/// `before|after` rules as a map from each page to the pages that must come after it
+pub fn ordering_lines_to_after_map(
+    lines: impl Iterator<Item = String>,
+) -> Result<HashMap<u64, HashSet<u64>>, ParseError> {
+    let mut ordering: HashMap<u64, HashSet<u64>> = HashMap::new();
+    for (left, right) in parse::lines_with(lines, line_to_rule)? {
+        if let Some(existing_afters) = ordering.get_mut(&left) {
+            existing_afters.insert(right);
+        } else {
+            let mut set = HashSet::new();
+            set.insert(right);
+            ordering.insert(left, set);
+        }
+    }
+
+    Ok(ordering)
+}
+
+pub fn pages_line_to_vec(pages_line: &str) -> Result<Vec<u64>, ParseError> {
+    pages_line.split(',').map(parse::number).collect()
+}
+
+/// Whether `page_vec` satisfies every ordering rule
+pub fn check_page_ordering(page_vec: &[u64], ordering: &HashMap<u64, HashSet<u64>>) -> bool {
+    let mut previous_pages = HashSet::new();
+    for page in page_vec {
+        if let Some(afters) = ordering.get(page)
+            && afters.intersection(&previous_pages).next().is_some()
+        {
+            return false;
+        }
+        previous_pages.insert(*page);
+    }
+
+    true
+}
+
+/// Reorder `page_vec` to satisfy the ordering rules, `None` if it already did
+pub fn fix_page_ordering(
+    mut page_vec: Vec<u64>,
+    ordering: &HashMap<u64, HashSet<u64>>,
+) -> Option<Vec<u64>> {
+    let mut updated = true;
+    let mut ever_updated = false;
+    'outer: while updated {
+        updated = false;
+        let mut previous_pages = HashMap::new();
+        for i in 0..page_vec.len() {
+            let page = page_vec[i];
+            if let Some(afters) = ordering.get(&page) {
+                for after in afters {
+                    if let Some(&prev_page_pos) = previous_pages.get(after) {
+                        updated = true;
+                        ever_updated = true;
+                        page_vec.swap(i, prev_page_pos);
+                        continue 'outer;
+                    }
+                }
+            }
+            previous_pages.insert(page, i);
+        }
+    }
+
+    if ever_updated { Some(page_vec) } else { None }
+}
+
+pub struct PrintQueue {
+    /// Page to the set of pages that must be printed after it
+    pub ordering: HashMap<u64, HashSet<u64>>,
+    pub updates: Vec<Vec<u64>>,
+}
+
+pub fn lines_to_print_queue(
+    lines_incl_empty: impl Iterator<Item = String>,
+) -> Result<PrintQueue, ParseError> {
+    let (ordering_lines, pages_lines) = separate_ordering_and_page_lines(lines_incl_empty)?;
+
+    // Updates start after the rules and the blank line
+    let first_update_line = ordering_lines.len() + 2;
+    let updates = parse::lines_with(pages_lines, pages_line_to_vec).map_err(|e| ParseError {
+        line: e.line.map(|l| l + first_update_line - 1),
+        ..e
+    })?;
+
+    Ok(PrintQueue {
+        ordering: ordering_lines_to_after_map(ordering_lines.into_iter())?,
+        updates,
+    })
+}
+
+/// [`lines_to_print_queue`] on the text of an input file
+pub fn parse(text: &str) -> Result<PrintQueue, ParseError> {
+    lines_to_print_queue(text.lines().map(|l| l.to_string()))
+}
+
+/// Part 1: sum of middle pages of correctly ordered updates
+pub fn process_pages(queue: &PrintQueue) -> u64 {
+    queue
+        .updates
+        .iter()
+        .filter(|pages| check_page_ordering(pages, &queue.ordering))
+        .map(|pages| pages[pages.len().div(2)])
+        .sum()
+}
+
+/// Part 2: sum of middle pages of incorrectly ordered updates, after fixing them
+pub fn process_pages_2(queue: &PrintQueue) -> u64 {
+    queue
+        .updates
+        .iter()
+        .filter_map(|pages| fix_page_ordering(pages.clone(), &queue.ordering))
+        .map(|pages| pages[pages.len().div(2)])
+        .sum()
+}
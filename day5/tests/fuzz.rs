@@ -0,0 +1 @@
+common::parser_fuzz_test!(day5::parse, seed = 5, inputs = ["input", "test"],);
@@ -0,0 +1,402 @@
+//! Day 6: Guard Gallivant
+
+use std::{
+    io::{self, BufRead},
+    path::Path,
+};
+
+use common::{
+    cancel::{CancelToken, Cancelled},
+    input,
+    parse::{self, ParseError},
+    progress::Progress,
+    puzzle::PuzzleId,
+    repl::{Repl, Simulation},
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 6);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// A map cell. Empty cells record the directions the guard has walked through them.
+#[derive(Clone)]
+pub enum MapPos {
+    Empty(
+        bool, /* up */
+        bool, /* down */
+        bool, /* left */
+        bool, /* right */
+    ),
+    Obstacle,
+}
+
+/// The map, which must have exactly one guard
+pub fn lines_to_grid(lines: impl Iterator<Item = String>) -> Result<Vec<Vec<MapPos>>, ParseError> {
+    let grid = parse::grid(lines, |c| ".#^v<>".contains(c))?;
+
+    let guards = grid
+        .iter()
+        .flatten()
+        .filter(|c| "^v<>".contains(**c))
+        .count();
+    if guards != 1 {
+        return Err(ParseError::new(format!(
+            "expected one guard, found {}",
+            guards
+        )));
+    }
+
+    Ok(grid
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|c| match c {
+                    '#' => MapPos::Obstacle,
+                    '^' => MapPos::Empty(true, false, false, false),
+                    'v' => MapPos::Empty(false, true, false, false),
+                    '<' => MapPos::Empty(false, false, true, false),
+                    '>' => MapPos::Empty(false, false, false, true),
+                    _ => MapPos::Empty(false, false, false, false),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// [`lines_to_grid`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<Vec<MapPos>>, ParseError> {
+    lines_to_grid(parse::text_lines(text))
+}
+
+enum SimUpdate {
+    Pos((usize, usize)),
+    Dir((isize, isize)),
+    Loop,
+    End,
+}
+
+fn next_step(grid: &[Vec<MapPos>], x: usize, y: usize, dir: (isize, isize)) -> SimUpdate {
+    // Sanity check current position
+    match grid[y][x] {
+        MapPos::Empty(up, down, left, right) => match dir {
+            (0, -1) => {
+                if !up {
+                    panic!("Current dir not set on grid");
+                }
+            }
+            (0, 1) => {
+                if !down {
+                    panic!("Current dir not set on grid");
+                }
+            }
+            (-1, 0) => {
+                if !left {
+                    panic!("Current dir not set on grid");
+                }
+            }
+            (1, 0) => {
+                if !right {
+                    panic!("Current dir not set on grid");
+                }
+            }
+            _ => panic!("Invalid direction"),
+        },
+        _ => panic!("Invalid start position"),
+    };
+
+    let x2 = x as isize + dir.0;
+    let y2 = y as isize + dir.1;
+
+    // Check if we are out of bounds
+    if x2 < 0 || y2 < 0 || y2 >= grid.len() as isize || x2 >= grid[0].len() as isize {
+        return SimUpdate::End;
+    }
+
+    match grid[y2 as usize][x2 as usize] {
+        MapPos::Empty(up, down, left, right) => {
+            let in_loop = match dir {
+                (0, -1) => up,
+                (0, 1) => down,
+                (-1, 0) => left,
+                (1, 0) => right,
+                _ => panic!("Invalid direction"),
+            };
+
+            if in_loop {
+                SimUpdate::Loop
+            } else {
+                SimUpdate::Pos((x2 as usize, y2 as usize))
+            }
+        }
+        MapPos::Obstacle => {
+            let dir = match dir {
+                (0, -1) => (1, 0),
+                (0, 1) => (-1, 0),
+                (-1, 0) => (0, -1),
+                (1, 0) => (0, 1),
+                _ => panic!("Invalid direction"),
+            };
+
+            SimUpdate::Dir(dir)
+        }
+    }
+}
+
+/// Walk the guard until they leave the map, marking visited cells. True if they end up in a loop.
+pub fn run_sim(grid: &mut [Vec<MapPos>]) -> bool {
+    let (mut start_pos, mut start_dir) = start_pos_and_dir(grid);
+
+    loop {
+        match next_step(grid, start_pos.0, start_pos.1, start_dir) {
+            SimUpdate::Pos(pos) => {
+                start_pos = pos;
+            }
+            SimUpdate::Dir(dir) => {
+                start_dir = dir;
+            }
+            SimUpdate::End => {
+                return false;
+            }
+            SimUpdate::Loop => {
+                return true;
+            }
+        }
+
+        mark_visited(grid, start_pos, start_dir);
+    }
+}
+
+fn mark_visited(grid: &mut [Vec<MapPos>], pos: (usize, usize), dir: (isize, isize)) {
+    match grid[pos.1][pos.0] {
+        MapPos::Empty(ref mut up, ref mut down, ref mut left, ref mut right) => match dir {
+            (0, -1) => {
+                *up = true;
+            }
+            (0, 1) => {
+                *down = true;
+            }
+            (-1, 0) => {
+                *left = true;
+            }
+            (1, 0) => {
+                *right = true;
+            }
+            _ => panic!("Invalid direction"),
+        },
+        _ => panic!("Invalid position"),
+    }
+}
+
+pub fn start_pos_and_dir(grid: &[Vec<MapPos>]) -> ((usize, usize), (isize, isize)) {
+    for (y, row) in grid.iter().enumerate() {
+        for (x, pos) in row.iter().enumerate() {
+            if let MapPos::Empty(up, down, left, right) = *pos {
+                if up {
+                    return ((x, y), (0, -1));
+                }
+                if down {
+                    return ((x, y), (0, 1));
+                }
+                if left {
+                    return ((x, y), (-1, 0));
+                }
+                if right {
+                    return ((x, y), (1, 0));
+                }
+            }
+        }
+    }
+
+    panic!("No start position found");
+}
+
+pub fn pos_visited(pos: &MapPos) -> bool {
+    match *pos {
+        MapPos::Empty(up, down, left, right) => up || down || left || right,
+        _ => false,
+    }
+}
+
+pub fn visited_pos_count(grid: &[Vec<MapPos>]) -> usize {
+    grid.iter()
+        .map(|row| row.iter().filter(|p| pos_visited(p)).count())
+        .sum()
+}
+
+/// Part 1: cells visited before the guard leaves the map
+pub fn visted_after_walk(grid: &[Vec<MapPos>]) -> usize {
+    let mut grid = grid.to_vec();
+    run_sim(&mut grid);
+
+    visited_pos_count(&grid)
+}
+
+/// The map after the guard's walk: `#` for obstacles, `X` for visited cells
+pub fn walked_grid(grid: &[Vec<MapPos>]) -> Vec<Vec<char>> {
+    let mut grid = grid.to_vec();
+    run_sim(&mut grid);
+
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|p| match p {
+                    MapPos::Obstacle => '#',
+                    p if pos_visited(p) => 'X',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether an obstacle at (x, y) would trap the guard in a loop
+pub fn add_obstacle_and_check_for_loop(mut grid: Vec<Vec<MapPos>>, x: usize, y: usize) -> bool {
+    match grid[y][x] {
+        MapPos::Empty(up, down, left, right) => {
+            // Guard location, can't add obstacle here
+            if up || down || left || right {
+                return false;
+            }
+
+            grid[y][x] = MapPos::Obstacle;
+
+            run_sim(&mut grid)
+        }
+        // Already an obstacle
+        _ => false,
+    }
+}
+
+/// Part 2: positions where a single new obstacle traps the guard in a loop
+/// Checks `cancel` once per candidate obstacle position
+pub fn loop_causing_obstacle_positions_count(
+    grid: &[Vec<MapPos>],
+    cancel: &CancelToken,
+) -> Result<usize, Cancelled> {
+    let mut progress = Progress::counted("obstacle positions", (grid.len() * grid[0].len()) as u64);
+    let mut cnt = 0;
+    for y in 0..grid.len() {
+        for x in 0..grid[0].len() {
+            cancel.check()?;
+            if add_obstacle_and_check_for_loop(grid.to_vec(), x, y) {
+                cnt += 1;
+            }
+            progress.tick();
+        }
+    }
+
+    Ok(cnt)
+}
+
+/// Step by step guard walk for [`walk_repl`]
+#[derive(Clone)]
+pub struct GuardWalk {
+    grid: Vec<Vec<MapPos>>,
+    pos: (usize, usize),
+    dir: (isize, isize),
+    ended: Option<&'static str>,
+}
+
+impl Simulation for GuardWalk {
+    fn step(&mut self, n: usize) -> usize {
+        for i in 0..n {
+            if self.ended.is_some() {
+                return i;
+            }
+
+            match next_step(&self.grid, self.pos.0, self.pos.1, self.dir) {
+                SimUpdate::Pos(pos) => {
+                    self.pos = pos;
+                }
+                SimUpdate::Dir(dir) => {
+                    self.dir = dir;
+                }
+                SimUpdate::End => {
+                    self.ended = Some("guard left the map");
+                    return i;
+                }
+                SimUpdate::Loop => {
+                    self.ended = Some("guard is stuck in a loop");
+                    return i;
+                }
+            }
+
+            mark_visited(&mut self.grid, self.pos, self.dir);
+        }
+
+        n
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+        for (y, row) in self.grid.iter().enumerate() {
+            for (x, pos) in row.iter().enumerate() {
+                out.push(if (x, y) == self.pos {
+                    match self.dir {
+                        (0, -1) => '^',
+                        (0, 1) => 'v',
+                        (-1, 0) => '<',
+                        _ => '>',
+                    }
+                } else if let MapPos::Obstacle = pos {
+                    '#'
+                } else if pos_visited(pos) {
+                    'X'
+                } else {
+                    '.'
+                });
+            }
+            out.push('\n');
+        }
+
+        out.push_str(&format!("visited: {}\n", visited_pos_count(&self.grid)));
+        if let Some(ended) = self.ended {
+            out.push_str(ended);
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Explore the walk interactively on stdin/stdout
+pub fn walk_repl(mut grid: Vec<Vec<MapPos>>) {
+    let (pos, dir) = start_pos_and_dir(&grid);
+    mark_visited(&mut grid, pos, dir);
+    let walk = GuardWalk {
+        grid,
+        pos,
+        dir,
+        ended: None,
+    };
+
+    Repl::new(walk)
+        .command("obstacle", "<x> <y>", "place an obstacle", |walk, args| {
+            let [x, y] = args else {
+                return Err("expected x and y".to_string());
+            };
+            let x: usize = x.parse().map_err(|_| format!("invalid x: {}", x))?;
+            let y: usize = y.parse().map_err(|_| format!("invalid y: {}", y))?;
+
+            match walk.grid.get(y).and_then(|row| row.get(x)) {
+                Some(MapPos::Empty(..)) if (x, y) != walk.pos => {
+                    walk.grid[y][x] = MapPos::Obstacle;
+                    Ok(format!("obstacle placed at ({}, {})", x, y))
+                }
+                Some(_) => Err(format!("({}, {}) is not free", x, y)),
+                None => Err(format!("({}, {}) is off the map", x, y)),
+            }
+        })
+        .run(io::stdin().lock(), io::stdout());
+}
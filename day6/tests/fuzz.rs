@@ -0,0 +1 @@
+common::parser_fuzz_test!(day6::parse, seed = 6, inputs = ["input", "test"],);
@@ -0,0 +1,145 @@
+//! Day 7: Bridge Repair
+
+use std::{io::BufRead, path::Path};
+
+use common::{
+    arith,
+    cancel::{CancelToken, Cancelled},
+    input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 7);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+/// Parse a `result: arg arg ...` equation
+pub fn line_to_peq(line: &str) -> Result<(u64, Vec<u64>), ParseError> {
+    let (result, args) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::new("expected result: args"))?;
+    let args = args
+        .split_ascii_whitespace()
+        .map(parse::number)
+        .collect::<Result<_, _>>()?;
+
+    Ok((parse::number(result)?, args))
+}
+
+pub fn lines_to_peqs(
+    lines: impl Iterator<Item = String>,
+) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    parse::lines_with(lines, line_to_peq)
+}
+
+/// [`lines_to_peqs`] on the text of an input file
+pub fn parse(text: &str) -> Result<Vec<(u64, Vec<u64>)>, ParseError> {
+    lines_to_peqs(parse::text_lines(text))
+}
+
+/// Whether `+` and `*` between `args`, evaluated left to right, can produce `result`
+pub fn sat<I>(result: u64, mut args: I, partial_result: u64) -> bool
+where
+    I: Iterator<Item = u64>,
+    I: Clone,
+{
+    let arg = if let Some(arg) = args.next() {
+        arg
+    } else {
+        return partial_result == result;
+    };
+
+    let add_result = arith::add(partial_result, arg);
+    let mul_result = arith::mul(partial_result, arg);
+
+    // No zeros, so no operation can reduce the partial result
+    if add_result <= result && sat(result, args.clone(), add_result) {
+        return true;
+    }
+
+    if mul_result <= result && sat(result, args, mul_result) {
+        return true;
+    }
+
+    false
+}
+
+/// [`sat`] with the `||` concatenation operator as well.
+/// This is exponential in the number of args, so it checks `cancel` at every node.
+pub fn sat_w_cat<I>(
+    result: u64,
+    mut args: I,
+    partial_result: u64,
+    cancel: &CancelToken,
+) -> Result<bool, Cancelled>
+where
+    I: Iterator<Item = u64>,
+    I: Clone,
+{
+    cancel.check()?;
+
+    let arg = if let Some(arg) = args.next() {
+        arg
+    } else {
+        return Ok(partial_result == result);
+    };
+
+    let add_result = arith::add(partial_result, arg);
+    let mul_result = arith::mul(partial_result, arg);
+    // Concatenate without any pesky string allocations
+    let cat_result = arith::add(
+        arith::mul(partial_result, arith::pow(10_u64, arg.ilog10() + 1)),
+        arg,
+    );
+
+    // No zeros, so no operation can reduce the partial result
+    if add_result <= result && sat_w_cat(result, args.clone(), add_result, cancel)? {
+        return Ok(true);
+    }
+
+    if mul_result <= result && sat_w_cat(result, args.clone(), mul_result, cancel)? {
+        return Ok(true);
+    }
+
+    if cat_result <= result && sat_w_cat(result, args, cat_result, cancel)? {
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// Part 1: sum of results of the equations that can be satisfied with [`sat`]
+pub fn result_sum_from_sat_peqs(peqs: &[(u64, Vec<u64>)]) -> u64 {
+    arith::sum(
+        peqs.iter()
+            .filter(|(result, args)| sat(*result, args.iter().cloned(), 0))
+            .map(|(result, _)| *result),
+    )
+}
+
+/// Part 2: sum of results of the equations that can be satisfied with [`sat_w_cat`]
+pub fn result_sum_from_sat_peqs_w_cat(
+    peqs: &[(u64, Vec<u64>)],
+    cancel: &CancelToken,
+) -> Result<u64, Cancelled> {
+    let mut sat_results = Vec::new();
+    for (result, args) in peqs {
+        if sat_w_cat(*result, args.iter().cloned(), 0, cancel)? {
+            sat_results.push(*result);
+        }
+    }
+
+    Ok(arith::sum(sat_results))
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day7::parse, seed = 7, inputs = ["input"],);
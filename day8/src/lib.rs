@@ -0,0 +1,141 @@
+//! Day 8: Resonant Collinearity
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::BufRead,
+    path::Path,
+};
+
+use common::{
+    combinatorics, input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 8);
+
+common::embed_inputs!("input", "test");
+
+/// Non-empty lines of `filename`
+pub fn read_lines<P>(filename: P) -> impl Iterator<Item = String>
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .filter(|l| !l.trim().is_empty())
+}
+
+pub struct AntennaMap {
+    /// Map (width, height)
+    pub bounds: (usize, usize),
+    /// Antenna positions by frequency
+    pub antennas: HashMap<char, Vec<(usize, usize)>>,
+}
+
+/// The antenna map, `.` for empty cells and letters or digits for antennas
+pub fn lines_to_antenna_map(lines: impl Iterator<Item = String>) -> Result<AntennaMap, ParseError> {
+    let grid = parse::grid(lines, |c| c == '.' || c.is_ascii_alphanumeric())?;
+
+    let mut antennas: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if c != '.' {
+                antennas.entry(c).or_default().push((x, y));
+            }
+        }
+    }
+
+    Ok(AntennaMap {
+        bounds: (grid[0].len(), grid.len()),
+        antennas,
+    })
+}
+
+/// [`lines_to_antenna_map`] on the text of an input file
+pub fn parse(text: &str) -> Result<AntennaMap, ParseError> {
+    lines_to_antenna_map(parse::text_lines(text))
+}
+
+/// In-bounds antinodes of every pair of same-frequency antennas
+pub fn antenna_poss_to_antinodes(
+    poss: &[(usize, usize)],
+    bounds: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    combinatorics::pairs(poss)
+        .flat_map(move |(&pos1, &pos2)| antenna_pair_to_antinodes(pos1, pos2, bounds))
+}
+
+/// [`antenna_poss_to_antinodes`] with resonant harmonics
+pub fn antenna_poss_to_antinodes2(
+    poss: &[(usize, usize)],
+    bounds: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    combinatorics::pairs(poss)
+        .flat_map(move |(&pos1, &pos2)| antenna_pair_to_antinodes2(pos1, pos2, bounds))
+}
+
+fn antenna_pair_to_antinodes(
+    pos1: (usize, usize),
+    pos2: (usize, usize),
+    bounds: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    let dx = pos2.0 as isize - pos1.0 as isize;
+    let dy = pos2.1 as isize - pos1.1 as isize;
+
+    let first = (pos1.0 as isize + (2 * dx), pos1.1 as isize + (2 * dy));
+    let second = (pos1.0 as isize - dx, pos1.1 as isize - dy);
+
+    [first, second]
+        .into_iter()
+        .filter(move |(x, y)| {
+            *x >= 0 && *y >= 0 && *x < bounds.0 as isize && *y < bounds.1 as isize
+        })
+        .map(|(x, y)| (x as usize, y as usize))
+}
+
+fn antenna_pair_to_antinodes2(
+    pos1: (usize, usize),
+    pos2: (usize, usize),
+    bounds: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    let dx = pos2.0 as isize - pos1.0 as isize;
+    let dy = pos2.1 as isize - pos1.1 as isize;
+
+    let inc = (0..)
+        .map(move |i| (pos1.0 as isize + (i * dx), pos1.1 as isize + (i * dy)))
+        .take_while(move |(x, y)| {
+            *x >= 0 && *y >= 0 && *x < bounds.0 as isize && *y < bounds.1 as isize
+        });
+
+    let dec = (1..)
+        .map(move |i| (pos1.0 as isize - (i * dx), pos1.1 as isize - (i * dy)))
+        .take_while(move |(x, y)| {
+            *x >= 0 && *y >= 0 && *x < bounds.0 as isize && *y < bounds.1 as isize
+        });
+
+    inc.chain(dec).map(|(x, y)| (x as usize, y as usize))
+}
+
+/// Part 1: unique antinode locations
+pub fn antinode_count(map: &AntennaMap) -> usize {
+    let antinodes = map
+        .antennas
+        .values()
+        .flat_map(|poss| antenna_poss_to_antinodes(poss, map.bounds))
+        .collect::<HashSet<_>>();
+
+    antinodes.len()
+}
+
+/// Part 2: unique antinode locations including resonant harmonics
+pub fn antinode_count2(map: &AntennaMap) -> usize {
+    let antinodes = map
+        .antennas
+        .values()
+        .flat_map(|poss| antenna_poss_to_antinodes2(poss, map.bounds))
+        .collect::<HashSet<_>>();
+
+    antinodes.len()
+}
@@ -0,0 +1 @@
+common::parser_fuzz_test!(day8::parse, seed = 8, inputs = ["input", "test"],);
@@ -0,0 +1,140 @@
+//! Day 9: Disk Fragmenter
+
+use std::{io::BufRead, path::Path};
+
+use common::{
+    arith, input,
+    parse::{self, ParseError},
+    puzzle::PuzzleId,
+};
+
+pub const PUZZLE: PuzzleId = PuzzleId::new(2024, 9);
+
+common::embed_inputs!("input", "test");
+
+/// First non-empty line of `filename`, empty if there is none
+pub fn read_line<P>(filename: P) -> String
+where
+    P: AsRef<Path>,
+{
+    input::open(PUZZLE, filename, EMBEDDED)
+        .lines()
+        .map(|l| l.unwrap())
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default()
+}
+
+/// Dense disk map, alternating file and free space lengths
+pub fn line_to_disk_map(line: &str) -> Result<Vec<u8>, ParseError> {
+    if line.is_empty() {
+        return Err(ParseError::new("empty disk map"));
+    }
+
+    line.chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| ParseError::new(format!("invalid digit {:?}", c)))
+        })
+        .collect()
+}
+
+/// [`line_to_disk_map`] on the first non-empty line of an input file
+pub fn parse(text: &str) -> Result<Vec<u8>, ParseError> {
+    line_to_disk_map(parse::text_lines(text).next().as_deref().unwrap_or(""))
+}
+
+/// Part 1: checksum after moving file blocks one at a time into the leftmost free space
+pub fn get_checksum(disk_map: &[u8]) -> usize {
+    let mut checksum = 0;
+    let chars = &mut disk_map.to_vec();
+    let mut j = chars.len() - 1;
+    j -= j % 2; // Start on a file
+    let mut idx: usize = 0;
+    for i in 0..chars.len() {
+        if i % 2 == 0 {
+            let ci = chars[i];
+            for x in 0..ci {
+                checksum = arith::add(checksum, arith::mul(i / 2, idx + x as usize));
+            }
+            idx += ci as usize;
+        } else {
+            while j > i + 1 && chars[j] > 0 && chars[i] > 0 {
+                let filled = u8::min(chars[j], chars[i]);
+                chars[i] -= filled;
+                chars[j] -= filled;
+
+                for x in 0..filled {
+                    checksum = arith::add(checksum, arith::mul(j / 2, idx + x as usize));
+                }
+                idx += filled as usize;
+
+                if chars[j] == 0 {
+                    // Move to the next file, skip blanks when coming this direction
+                    j -= 2;
+                }
+            }
+        }
+
+        if i == j {
+            break;
+        }
+    }
+
+    checksum
+}
+
+/// Part 2: checksum after moving whole files into the leftmost span that fits them
+pub fn get_contig_checksum(disk_map: &[u8]) -> usize {
+    let mut checksum = 0;
+    let chars = &mut disk_map.to_vec();
+    let mut j = (chars.len() - 1) as isize;
+    j -= j % 2; // Start on a file
+
+    let mut idxs = {
+        let mut running_sum = 0;
+        chars
+            .iter()
+            .map(|c| {
+                let idx = running_sum;
+                running_sum += *c as usize;
+                idx
+            })
+            .collect::<Vec<_>>()
+    };
+
+    while j >= 0 {
+        {
+            let j = j as usize;
+            let mut i = 1;
+            while i < j {
+                if chars[i] >= chars[j] {
+                    chars[i] -= chars[j];
+
+                    for x in 0..chars[j] {
+                        checksum = arith::add(checksum, arith::mul(j / 2, idxs[i] + x as usize));
+                    }
+
+                    // "Merge" the file into the previous one (just for idx calculation)
+                    idxs[i] += chars[j] as usize;
+
+                    chars[j] = 0;
+
+                    break;
+                }
+
+                i += 2;
+            }
+
+            if chars[j] > 0 {
+                for x in 0..chars[j] {
+                    checksum = arith::add(checksum, arith::mul(j / 2, idxs[j] + x as usize));
+                }
+            }
+        }
+
+        j -= 2;
+    }
+
+    checksum
+}
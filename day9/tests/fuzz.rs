@@ -0,0 +1,7 @@
+common::parser_fuzz_test!(
+    day9::parse,
+    seed = 9,
+    inputs = ["input", "test"],
+    // Inputs that once made the parser panic
+    regressions = [b"\n-1", b"",],
+);